@@ -1,3 +1,4 @@
+use advent_of_code::template::visualize::{Color, Frame, Visualizer};
 use itertools::Itertools;
 use std::collections::HashSet;
 
@@ -32,6 +33,15 @@ impl Direction {
             Direction::W => Direction::E,
         }
     }
+
+    fn char(&self) -> char {
+        match self {
+            Direction::N => '^',
+            Direction::E => '>',
+            Direction::S => 'v',
+            Direction::W => '<',
+        }
+    }
 }
 
 #[derive(Debug, Hash, Eq, PartialEq, Copy, Clone)]
@@ -190,6 +200,24 @@ impl Board {
         }
         panic!("No guard found");
     }
+
+    fn frame(&self, guard: &MovingPoint, visited: &HashSet<Point>) -> Frame {
+        let mut frame = Frame::from_fn(self.width, self.height, |x, y| match self.board[y][x] {
+            '#' => '#',
+            _ => '.',
+        });
+        frame.set(guard.x as usize, guard.y as usize, guard.direction.char());
+        frame.highlight(visited.iter().map(|p| (p.x as usize, p.y as usize)))
+    }
+}
+
+fn visualizer() -> Option<Visualizer> {
+    Visualizer::new().map(|v| {
+        Direction::iter()
+            .fold(v, |v, direction| v.color(direction.char(), Color::Green))
+            .color('#', Color::Gray)
+            .color('O', Color::Red)
+    })
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
@@ -197,6 +225,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     let mut point = board.guard_position();
     let mut positions: HashSet<Point> = HashSet::new();
     positions.insert(point.into());
+    let mut visualizer = visualizer();
 
     while !looking_away(&board, &point) {
         let new_point = point.move_once();
@@ -206,6 +235,15 @@ pub fn part_one(input: &str) -> Option<u32> {
             point = new_point;
         }
         positions.insert(point.into());
+
+        if let Some(visualizer) = &mut visualizer {
+            visualizer.show(|| board.frame(&point, &positions));
+        }
+    }
+
+    if let Some(visualizer) = &mut visualizer {
+        let caption = format!("{} distinct positions", positions.len());
+        visualizer.finish(&board.frame(&point, &positions).caption(caption));
     }
 
     Some(positions.len() as u32)
@@ -237,12 +275,22 @@ pub fn part_two(input: &str) -> Option<u32> {
 
     positions.remove(&guard_start.into());
 
-    Some(
-        positions
-            .iter()
-            .filter(|point| can_place_obstacle(&board, guard_start, &point))
-            .count() as u32,
-    )
+    let obstacles: Vec<&Point> = positions
+        .iter()
+        .filter(|point| can_place_obstacle(&board, guard_start, &point))
+        .collect();
+
+    if let Some(mut visualizer) = visualizer() {
+        let mut frame = board
+            .frame(&guard_start, &positions)
+            .caption(format!("{} possible obstructions", obstacles.len()));
+        for obstacle in &obstacles {
+            frame.set(obstacle.x as usize, obstacle.y as usize, 'O');
+        }
+        visualizer.finish(&frame);
+    }
+
+    Some(obstacles.len() as u32)
 }
 
 fn can_place_obstacle(board: &Board, mut guard: MovingPoint, position: &Point) -> bool {
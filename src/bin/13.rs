@@ -1,3 +1,4 @@
+use advent_of_code::debug;
 use itertools::Itertools;
 use regex::Regex;
 
@@ -11,7 +12,7 @@ struct Move {
 
 pub fn part_one(input: &str) -> Option<u32> {
     let machines = parse_input(input);
-    println!("{:?}", machines);
+    debug!("{:?}", machines);
     let mut result = 0;
     machines.iter().for_each(|(a, b, prize)| {
         if let Some((A, B)) = solve(a, b, prize) {
@@ -30,20 +31,20 @@ fn solve(a: &Move, b: &Move, prize: &Move) -> Option<(i64, i64)> {
     let a_solved = (xp - (xb * b_solved)) / xa;
 
     if xp == a_solved * xa + b_solved * xb && yp == a_solved * ya + b_solved * yb {
-        println!(
+        debug!(
             "Solution found for {:?} {:?} {:?}: A: {}, B: {}",
             a, b, prize, a_solved, b_solved
         );
         Some((a_solved, b_solved))
     } else {
-        println!("No solution found for {:?} {:?} {:?}", a, b, prize);
+        debug!("No solution found for {:?} {:?} {:?}", a, b, prize);
         None
     }
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
     let machines = parse_input(input);
-    println!("{:?}", machines);
+    debug!("{:?}", machines);
     let mut result = 0;
     machines.iter().for_each(|(a, b, prize)| {
         let new_prize = Move {
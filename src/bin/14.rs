@@ -1,3 +1,5 @@
+use advent_of_code::debug;
+use advent_of_code::template::visualize::{Color, Frame, Visualizer};
 use itertools::Itertools;
 use regex::Regex;
 use std::collections::HashMap;
@@ -59,6 +61,7 @@ pub fn part_two(input: &str) -> Option<u32> {
     let mut robots = parse_input(input);
     let width = 101;
     let height = 103;
+    let mut visualizer = Visualizer::new().map(|v| v.color('#', Color::Green).fps(60));
 
     for i in 1..10000000 {
         for robot in &mut robots {
@@ -67,21 +70,29 @@ pub fn part_two(input: &str) -> Option<u32> {
         }
 
         if find_vertical(&mut robots, 10) {
-            println!("Vertical found at {}", i);
-            let mut boardr = vec![vec!['.'; width as usize]; height as usize];
-            for robot in &robots {
-                boardr[robot.0.y as usize][robot.0.x as usize] = '#';
-            }
-            for row in boardr {
-                println!("{}", row.iter().collect::<String>());
+            debug!("Vertical found at {}", i);
+            if let Some(visualizer) = &mut visualizer {
+                visualizer.finish(&frame(&robots, width, height).caption(format!("{i} seconds")));
             }
             return Some(i);
         }
+
+        if let Some(visualizer) = &mut visualizer {
+            visualizer.show(|| frame(&robots, width, height).caption(format!("{i} seconds")));
+        }
     }
 
     None
 }
 
+fn frame(robots: &[(Point, Velocity)], width: i32, height: i32) -> Frame {
+    let mut frame = Frame::from_fn(width as usize, height as usize, |_, _| '.');
+    for robot in robots {
+        frame.set(robot.0.x as usize, robot.0.y as usize, '#');
+    }
+    frame
+}
+
 fn find_vertical(robots: &mut Vec<(Point, Velocity)>, size: u32) -> bool {
     let mut by_x = HashMap::new();
     robots.iter().for_each(|(point, _)| {
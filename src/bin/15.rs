@@ -1,3 +1,5 @@
+use advent_of_code::debug;
+use advent_of_code::template::visualize::{Color, Frame, Visualizer};
 use std::fmt::{Display, Formatter, Pointer};
 use std::ops::Mul;
 use strum::IntoEnumIterator;
@@ -5,6 +7,16 @@ use strum_macros::{Display, EnumIter};
 
 advent_of_code::solution!(15);
 
+fn visualizer() -> Option<Visualizer> {
+    Visualizer::new().map(|v| {
+        v.color('#', Color::Gray)
+            .color('@', Color::Red)
+            .color('O', Color::Yellow)
+            .color('[', Color::Yellow)
+            .color(']', Color::Yellow)
+    })
+}
+
 struct Board {
     board: Vec<Vec<Cell>>,
     width: usize,
@@ -181,10 +193,8 @@ impl Board {
         self.board[point.y as usize][point.x as usize] = Cell::from_char(ch, point);
     }
 
-    fn print(&self) {
-        for row in self.board.iter() {
-            println!("{}", row.iter().map(|c| c.char()).collect::<String>());
-        }
+    fn frame(&self) -> Frame {
+        Frame::from_fn(self.width, self.height, |x, y| self.board[y][x].char())
     }
 
     fn try_move(&mut self, cell: Cell, direction: Direction) -> bool {
@@ -260,7 +270,7 @@ impl Board {
 
 pub fn part_one(input: &str) -> Option<u32> {
     let (mut board, directions) = parse_input(input);
-    board.print();
+    let mut visualizer = visualizer();
 
     let mut robot = board.get_robot();
     for direction in directions {
@@ -269,8 +279,13 @@ pub fn part_one(input: &str) -> Option<u32> {
                 robot = board.get(&p.move_to(&direction)).unwrap();
             }
         }
+        if let Some(visualizer) = &mut visualizer {
+            visualizer.show(|| board.frame().caption(format!("Move {direction}")));
+        }
+    }
+    if let Some(visualizer) = &mut visualizer {
+        visualizer.finish(&board.frame());
     }
-    board.print();
     let mut gps = 0;
     for row in board.board.iter() {
         for cell in row.iter() {
@@ -299,10 +314,8 @@ impl ScaledBoard {
         }
     }
 
-    fn print(&self) {
-        for row in self.board.iter() {
-            println!("{}", row.iter().map(|c| c.char()).collect::<String>());
-        }
+    fn frame(&self) -> Frame {
+        Frame::from_fn(self.width, self.height, |x, y| self.board[y][x].char())
     }
 
     fn do_move(&mut self, cell: ScaledCell, direction: &Direction) {
@@ -335,7 +348,7 @@ impl ScaledBoard {
         let new_p = p.move_to(&direction);
         match self.get(&new_p) {
             Some(next_cell) if self.try_move(next_cell, direction) => {
-                println!("Can move {} to {}, ({}, {})", next_cell, direction, p.x, p.y);
+                debug!("Can move {} to {}, ({}, {})", next_cell, direction, p.x, p.y);
                 true
             }
             _ => false
@@ -388,10 +401,10 @@ pub fn part_two(input: &str) -> Option<u32> {
     let (mut board, directions) = parse_input(input);
 
     let mut scaled_board = board.as_scaled_board();
-    scaled_board.print();
+    let mut visualizer = visualizer();
 
     let mut robot = scaled_board.get_robot();
-    println!("{:?}", robot);
+    debug!("{:?}", robot);
     for direction in directions {
         if scaled_board.try_move(robot, &direction) {
             scaled_board.do_move(robot, &direction);
@@ -399,9 +412,14 @@ pub fn part_two(input: &str) -> Option<u32> {
                 robot = scaled_board.get(&p.move_to(&direction)).unwrap();
             }
         }
+        if let Some(visualizer) = &mut visualizer {
+            visualizer.show(|| scaled_board.frame().caption(format!("Move {direction}")));
+        }
     }
 
-    scaled_board.print();
+    if let Some(visualizer) = &mut visualizer {
+        visualizer.finish(&scaled_board.frame());
+    }
     let mut gps = 0;
     for row in scaled_board.board.iter() {
         for cell in row.iter() {
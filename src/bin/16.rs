@@ -1,3 +1,4 @@
+use advent_of_code::template::visualize::{Color, Frame, Visualizer};
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use std::io::empty;
@@ -7,6 +8,16 @@ use strum_macros::{Display, EnumIter};
 
 advent_of_code::solution!(16);
 
+fn visualizer() -> Option<Visualizer> {
+    Visualizer::new().map(|v| {
+        v.color('#', Color::Gray)
+            .color('S', Color::Green)
+            .color('E', Color::Red)
+            .highlight(Color::Cyan)
+            .fps(60)
+    })
+}
+
 #[derive(Debug, Hash, Eq, PartialEq, Copy, Clone)]
 enum CellType {
     Wall,
@@ -162,10 +173,9 @@ impl Board {
         self.board[point.y as usize][point.x as usize] = Cell::new(CellType::from_char(ch), *point);
     }
 
-    fn print(&self) {
-        for row in self.board.iter() {
-            println!("{}", row.iter().map(|c| c.cell_type.char()).collect::<String>());
-        }
+    fn frame<'a>(&self, highlighted: impl IntoIterator<Item = &'a Point>) -> Frame {
+        Frame::from_fn(self.width, self.height, |x, y| self.board[y][x].cell_type.char())
+            .highlight(highlighted.into_iter().map(|p| (p.x as usize, p.y as usize)))
     }
 
     fn get_start(&self) -> Cell {
@@ -306,12 +316,20 @@ pub fn part_one(input: &str) -> Option<u32> {
     dq.push(Entry::new(start, Direction::E,0));
 
     let mut dist = HashMap::new();
+    let mut visualizer = visualizer();
     while !dq.is_empty() {
         let entry = dq.pop().unwrap();
         if dist.contains_key(&entry.cell.point) && dist.get(&entry.cell.point).unwrap() <= &entry.score {
             continue;
         }
         dist.insert(entry.cell.point, entry.score);
+        if let Some(visualizer) = &mut visualizer {
+            if dist.len() % 10 == 0 {
+                visualizer.show(|| {
+                    board.frame(dist.keys()).caption(format!("Score {}", entry.score))
+                });
+            }
+        }
         if entry.cell.point == finish.point {
             continue;
         }
@@ -321,6 +339,11 @@ pub fn part_one(input: &str) -> Option<u32> {
         }
     }
 
+    if let Some(visualizer) = &mut visualizer {
+        let caption = format!("Score {}", dist[&finish.point]);
+        visualizer.finish(&board.frame(dist.keys()).caption(caption));
+    }
+
     Some(dist.get(&finish.point).copied().unwrap())
 }
 
@@ -396,6 +419,11 @@ pub fn part_two(input: &str) -> Option<u32> {
         });
     }
 
+    if let Some(mut visualizer) = visualizer() {
+        let caption = format!("{} tiles on a best path", result.len());
+        visualizer.finish(&board.frame(&result).caption(caption));
+    }
+
     Some(result.len() as u32)
 }
 
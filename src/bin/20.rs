@@ -1,3 +1,4 @@
+use advent_of_code::debug;
 use std::collections::{HashSet, VecDeque};
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
@@ -106,7 +107,7 @@ impl Board {
 
     fn print(&self) {
         for row in self.board.iter() {
-            println!("{}", row.iter().collect::<String>());
+            debug!("{}", row.iter().collect::<String>());
         }
     }
 }
@@ -1,3 +1,4 @@
+use advent_of_code::debug;
 use std::collections::{HashMap, VecDeque};
 use itertools::Itertools;
 
@@ -33,31 +34,31 @@ pub fn part_one(input: &str) -> Option<u32> {
         let mut current = 'A';
         let mut instructions = String::new();
         for c in code.chars() {
-            println!("{} -> {}", current, c);
+            debug!("{} -> {}", current, c);
             let first_robot_moves = keypad_moves
                 .get(&current).unwrap()
                 .get(&c).unwrap();
-            println!("first_robot_moves: {}", first_robot_moves);
+            debug!("first_robot_moves: {}", first_robot_moves);
             let first_arrow_moves = first_robot_moves.chars()
                 .map(|c| arrow_pad_moves.get(&c).unwrap()
                     .get(&current).unwrap()).join("");
-            println!("first_arrow_moves: {}", first_arrow_moves);
+            debug!("first_arrow_moves: {}", first_arrow_moves);
             let second_arrow_moves = first_arrow_moves.chars()
                 .map(|c| arrow_pad_moves.get(&c).unwrap()
                     .get(&c).unwrap()).join("");
-            println!("second_arrow_moves: {}", second_arrow_moves);
+            debug!("second_arrow_moves: {}", second_arrow_moves);
             let my_moves = second_arrow_moves.chars()
                 .map(|c| keypad_moves.get(&c).unwrap()
                     .get(&c).unwrap()).join("");
-            println!("my_moves: {}", my_moves);
+            debug!("my_moves: {}", my_moves);
             current = c;
             instructions = format!("{}{}", instructions, my_moves.as_str());
         }
         instructions.len() as u32 * number(code)
     }).sum();
 
-    println!("{:?}", keypad_moves);
-    println!("{:?}", arrow_pad_moves);
+    debug!("{:?}", keypad_moves);
+    debug!("{:?}", arrow_pad_moves);
     Some(result)
 }
 
@@ -1,97 +1,302 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, leaderboard, new_day, read, report, scaffold, solve, stats, status, submissions,
+    time, verify, watch,
+};
+use advent_of_code::template::{cli, Config};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::commands::today;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::cli::{self, Shell};
+    use advent_of_code::template::commands::scaffold::ScaffoldOptions;
+    use advent_of_code::template::RunOptions;
+    use advent_of_code::template::{Config, Day, InputSource};
+    use std::{env, process};
 
     pub enum AppArguments {
         Download {
             day: Day,
         },
+        NewDay {
+            day: Day,
+            template: String,
+        },
         Read {
             day: Day,
         },
+        Report {
+            output: Option<String>,
+        },
         Scaffold {
             day: Day,
             download: bool,
-            overwrite: bool,
+            options: ScaffoldOptions,
         },
         Solve {
             day: Day,
-            release: bool,
-            dhat: bool,
+            options: RunOptions,
             submit: Option<u8>,
+            yes: bool,
+            watch: bool,
+            input: InputSource,
         },
         All {
             release: bool,
         },
+        Status {
+            readme: bool,
+        },
+        Submissions {
+            day: Option<Day>,
+        },
+        Stats {
+            file: Option<String>,
+            readme: bool,
+        },
+        Leaderboard {
+            id: String,
+            day: Option<Day>,
+        },
+        Verify {
+            day: Day,
+            dir: Option<String>,
+            release: bool,
+        },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            options: RunOptions,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            wait: bool,
+        },
+        Completions {
+            shell: Shell,
+        },
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+    impl AppArguments {
+        /// The day a command was given, if any.
+        fn day(&self) -> Option<Day> {
+            match self {
+                Self::Download { day }
+                | Self::NewDay { day, .. }
+                | Self::Read { day }
+                | Self::Scaffold { day, .. }
+                | Self::Solve { day, .. }
+                | Self::Verify { day, .. } => Some(*day),
+                Self::Submissions { day }
+                | Self::Leaderboard { day, .. }
+                | Self::Time { day, .. } => *day,
+                _ => None,
+            }
+        }
+    }
+
+    /// Load the configuration, applying the global `--config <path>`, `--year <year>` and `--set <key>=<value>` options.
+    fn parse_config(args: &mut pico_args::Arguments) -> Result<Config, Box<dyn std::error::Error>> {
+        let path: Option<String> = args.opt_value_from_str("--config")?;
+        let year: Option<u16> = args.opt_value_from_str("--year")?;
+        let overrides: Vec<String> = args.values_from_str("--set")?;
+
+        let mut config = Config::load_from(path.as_deref())?;
+        for value in &overrides {
+            config.apply_override(value)?;
+        }
+        if year.is_some() {
+            config.year = year;
+        }
+
+        Ok(config)
+    }
+
+    /// Print help for a command, or for the CLI if no command is given, and exit.
+    fn print_help(command: Option<&str>) -> ! {
+        match command {
+            None => print!("{}", cli::help()),
+            Some(name) => match cli::find(name) {
+                Some(command) => print!("{}", cli::command_help(command)),
+                None => {
+                    eprintln!("Error: {}", cli::unknown_command(name));
+                    process::exit(1);
+                }
+            },
+        }
+        process::exit(0);
+    }
+
+    pub fn parse() -> Result<(Config, AppArguments), Box<dyn std::error::Error>> {
+        let raw_args: Vec<String> = env::args().skip(1).collect();
         let mut args = pico_args::Arguments::from_env();
+        let is_help = args.contains(["-h", "--help"]);
+        let subcommand = args.subcommand()?;
 
-        let app_args = match args.subcommand()?.as_deref() {
+        match subcommand.as_deref() {
+            None if is_help => print_help(None),
+            None => {
+                eprint!("No command specified.\n\n{}", cli::help());
+                process::exit(1);
+            }
+            Some("help") => print_help(args.opt_free_from_str::<String>()?.as_deref()),
+            Some(name) => {
+                let command = cli::find(name).ok_or_else(|| cli::unknown_command(name))?;
+                if is_help {
+                    print_help(Some(name));
+                }
+                cli::validate(command, &raw_args[1..])?;
+            }
+        }
+
+        let config = parse_config(&mut args)?;
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
             },
+            Some("status") => AppArguments::Status {
+                readme: args.contains("--readme"),
+            },
+            Some("submissions") => AppArguments::Submissions {
+                day: args.opt_free_from_str()?,
+            },
+            Some("stats") => AppArguments::Stats {
+                file: args.opt_value_from_str("--file")?,
+                readme: args.contains("--readme"),
+            },
+            Some("leaderboard") => {
+                let day = args.opt_value_from_str("--day")?;
+                AppArguments::Leaderboard {
+                    id: args.free_from_str()?,
+                    day,
+                }
+            }
+            Some("verify") => AppArguments::Verify {
+                day: args.free_from_str()?,
+                dir: args.opt_value_from_str("--dir")?,
+                release: args.contains("--release"),
+            },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let options = RunOptions {
+                    release: true,
+                    dhat: args.contains("--dhat"),
+                    count_allocs: args.contains("--count-allocs"),
+                    cost: args.contains("--cost"),
+                    ..RunOptions::default()
+                };
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    options,
                 }
             }
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
-            Some("read") => AppArguments::Read {
+            Some("new-day") => AppArguments::NewDay {
                 day: args.free_from_str()?,
+                template: args
+                    .opt_value_from_str("--template")?
+                    .unwrap_or(ScaffoldOptions::default().template),
             },
-            Some("scaffold") => AppArguments::Scaffold {
+            Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
-                download: args.contains("--download"),
-                overwrite: args.contains("--overwrite"),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+            Some("report") => AppArguments::Report {
+                output: args.opt_value_from_str("--output")?,
             },
-            #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
+            Some("scaffold") => {
+                let defaults = ScaffoldOptions::default();
+                AppArguments::Scaffold {
+                    day: args.free_from_str()?,
+                    download: args.contains("--download"),
+                    options: ScaffoldOptions {
+                        overwrite: args.contains("--overwrite"),
+                        dry_run: args.contains("--dry-run"),
+                        template: args
+                            .opt_value_from_str("--template")?
+                            .unwrap_or(defaults.template),
+                    },
+                }
             }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
+            Some("solve") => {
+                let day = args.free_from_str()?;
+                let options = RunOptions {
+                    release: args.contains("--release"),
+                    dhat: args.contains("--dhat"),
+                    count_allocs: args.contains("--count-allocs"),
+                    profile_cpu: args.contains("--profile-cpu"),
+                    cost: args.contains("--cost"),
+                    visualize: args.contains("--visualize"),
+                    export: args.opt_value_from_str("--export")?,
+                    verbose: args.contains("--verbose"),
+                };
+                let submit: Option<u8> = args.opt_value_from_str("--submit")?;
+                if submit.is_some_and(|part| part != 1 && part != 2) {
+                    return Err("`--submit` expects part 1 or 2.".into());
+                }
+
+                let yes = args.contains("--yes");
+                let watch = args.contains("--watch");
+                let input_path: Option<String> = args.opt_value_from_str("--input")?;
+
+                // `--example` takes an optional name, so it needs to be parsed after all other options.
+                // A bare `--example` may be followed by its name, `--example=<name>` is picked up below.
+                let input = match input_path.as_deref() {
+                    Some("-") => InputSource::Stdin,
+                    Some(path) => InputSource::File(path.into()),
+                    None if args.contains("--example") => {
+                        InputSource::Example(args.opt_free_from_str()?)
+                    }
+                    None => match args.opt_value_from_str("--example")? {
+                        Some(name) => InputSource::Example(Some(name)),
+                        None => InputSource::Puzzle,
+                    },
+                };
+
+                AppArguments::Solve {
+                    day,
+                    options,
+                    submit,
+                    yes,
+                    watch,
+                    input,
+                }
+            }
+            #[cfg(feature = "today")]
+            Some("today") => AppArguments::Today {
+                wait: args.contains("--wait"),
+            },
+            #[cfg(not(feature = "today"))]
+            Some("today") => {
+                return Err(
+                    "`today` requires the `today` feature, run it via `cargo today`.".into(),
+                )
             }
+            Some("completions") => AppArguments::Completions {
+                shell: args.free_from_str()?,
+            },
+            _ => unreachable!("commands are checked before parsing their arguments"),
         };
 
         let remaining = args.finish();
         if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
+            return Err(format!("unexpected argument(s): {remaining:?}.").into());
         }
 
-        Ok(app_args)
+        // stored data may contain days of other events, only days given on the command line are checked.
+        if let Some(day) = app_args.day() {
+            config.check_day(day)?;
+        }
+
+        Ok((config, app_args))
     }
 }
 
@@ -101,44 +306,54 @@ fn main() {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
-                day,
-                download,
-                overwrite,
-            } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
-                }
+        Ok((config, args)) => run(&config, args),
+    };
+}
+
+fn run(config: &Config, args: AppArguments) {
+    match args {
+        AppArguments::All { release } => all::handle(config, release),
+        AppArguments::Completions { shell } => print!("{}", cli::completions(shell)),
+        AppArguments::Status { readme } => status::handle(config, readme),
+        AppArguments::Submissions { day } => submissions::handle(config, day),
+        AppArguments::Stats { file, readme } => stats::handle(config, file, readme),
+        AppArguments::Leaderboard { id, day } => leaderboard::handle(config, &id, day),
+        AppArguments::Verify { day, dir, release } => verify::handle(config, day, dir, release),
+        AppArguments::Time {
+            day,
+            all,
+            store,
+            options,
+        } => time::handle(config, day, all, store, options),
+        AppArguments::Download { day } => download::handle(config, day),
+        AppArguments::NewDay { day, template } => new_day::handle(config, day, &template),
+        AppArguments::Read { day } => read::handle(config, day),
+        AppArguments::Report { output } => report::handle(config, output),
+        AppArguments::Scaffold {
+            day,
+            download,
+            options,
+        } => {
+            scaffold::handle(config, day, &options);
+            if download && !options.dry_run {
+                download::handle(config, day);
             }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
+        }
+        AppArguments::Solve {
+            day,
+            options,
+            submit,
+            yes,
+            watch,
+            input,
+        } => {
+            if watch {
+                watch::handle(config, day, options);
+            } else {
+                solve::handle(config, day, options, submit, yes, &input);
             }
-        },
-    };
+        }
+        #[cfg(feature = "today")]
+        AppArguments::Today { wait } => today::handle(config, wait),
+    }
 }
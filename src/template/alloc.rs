@@ -0,0 +1,92 @@
+/// A lightweight global allocator that counts allocations.
+/// Installed by the `solution!` macro when the `count-allocs` feature is active.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+
+pub struct CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+}
+
+fn record(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(size as u64, Ordering::Relaxed);
+}
+
+/// Number of allocations and allocated bytes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocCount {
+    pub allocations: u64,
+    pub bytes: u64,
+}
+
+impl AllocCount {
+    /// Read the counters of the global allocator.
+    pub fn now() -> Self {
+        Self {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            bytes: BYTES.load(Ordering::Relaxed),
+        }
+    }
+
+    /// The allocations that happened between `earlier` and `self`.
+    #[must_use]
+    pub fn since(self, earlier: Self) -> Self {
+        Self {
+            allocations: self.allocations - earlier.allocations,
+            bytes: self.bytes - earlier.bytes,
+        }
+    }
+}
+
+impl Display for AllocCount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} allocs, {} B", self.allocations, self.bytes)
+    }
+}
+
+impl FromStr for AllocCount {
+    type Err = String;
+
+    /// Parses the format written by [`Display`], e.g. `3 allocs, 1024 B`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (allocations, bytes) = s
+            .split_once(',')
+            .ok_or_else(|| format!("could not parse allocation count: {s}"))?;
+
+        let number = |x: &str| {
+            x.split_whitespace()
+                .next()
+                .and_then(|n| n.parse::<u64>().ok())
+                .ok_or_else(|| format!("could not parse allocation count: {s}"))
+        };
+
+        Ok(AllocCount {
+            allocations: number(allocations)?,
+            bytes: number(bytes)?,
+        })
+    }
+}
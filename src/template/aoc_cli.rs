@@ -1,16 +1,27 @@
 /// Wrapper module around the "aoc-cli" command-line.
+///
+/// The tests call a fake `aoc` executable that talks to the test server, see [`crate::template::test_server`].
 use std::{
+    env,
     fmt::Display,
-    process::{Command, Output, Stdio},
+    fs,
+    process::{Command, Stdio},
 };
 
-use crate::template::Day;
+use crate::template::submission::{self, Submission};
+use crate::template::{http, Clock, Config, Day, SystemClock};
+
+/// The Advent of Code server, which needs a session cookie for personal pages.
+pub const OFFICIAL_URL: &str = "https://adventofcode.com";
 
 #[derive(Debug)]
 pub enum AocCommandError {
     CommandNotFound,
     CommandNotCallable,
-    BadExitStatus(Output),
+    /// the command failed, with its captured output.
+    BadExitStatus(String),
+    /// the server asked to wait before submitting again, with the seconds left.
+    Cooldown(u64),
 }
 
 impl Display for AocCommandError {
@@ -21,6 +32,12 @@ impl Display for AocCommandError {
             AocCommandError::BadExitStatus(_) => {
                 write!(f, "aoc-cli exited with a non-zero status.")
             }
+            AocCommandError::Cooldown(seconds) => write!(
+                f,
+                "the server asked to wait before submitting again, {}m {:02}s left.",
+                seconds / 60,
+                seconds % 60
+            ),
         }
     }
 }
@@ -33,72 +50,119 @@ pub fn check() -> Result<(), AocCommandError> {
     Ok(())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
+/// Reads the puzzle description, saving it to the puzzle file.
+pub fn read(config: &Config, day: Day) -> Result<String, AocCommandError> {
+    call_aoc_cli(&read_args(config, day))
+}
+
+/// Downloads the input and the puzzle description of a day.
+pub fn download(config: &Config, day: Day) -> Result<String, AocCommandError> {
+    let output = call_aoc_cli(&download_args(config, day))?;
+    println!("---");
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        config.input_path(day)
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        config.puzzle_path(day)
+    );
+    Ok(output)
+}
+
+/// Submits an answer, returning the response of the server.
+/// Every submission is logged, no answer is submitted while the cooldown of the previous submission is running.
+pub fn submit(
+    config: &Config,
+    day: Day,
+    part: u8,
+    result: &str,
+) -> Result<String, AocCommandError> {
+    let log = submission::read_log(&config.paths.submissions);
+    let now = u64::try_from(SystemClock.now()).unwrap_or(0);
+    if let Some(seconds) = submission::remaining_cooldown(&log, config.year, day, now) {
+        return Err(AocCommandError::Cooldown(seconds));
+    }
+
+    let output = submit_answer(config, day, part, result);
+
+    if let Ok(response) | Err(AocCommandError::BadExitStatus(response)) = &output {
+        let submission = Submission {
+            year: config.year,
+            day,
+            part,
+            answer: result.into(),
+            timestamp: now,
+            response: response.trim().into(),
+        };
+        if let Err(e) = submission::append_to_log(&config.paths.submissions, &submission) {
+            eprintln!("Failed to log the submission: {e}");
+        }
+    }
+
+    output
+}
+
+fn submit_answer(
+    config: &Config,
+    day: Day,
+    part: u8,
+    result: &str,
+) -> Result<String, AocCommandError> {
+    // the response is captured to record the verdict, echo it so it is still shown.
+    let output = Command::new("aoc")
+        .args(submit_args(config, day, part, result))
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
 
-    let args = build_args(
+    let response = command_result(output.status.success(), &output.stdout);
+    if let Ok(text) | Err(AocCommandError::BadExitStatus(text)) = &response {
+        print!("{text}");
+    }
+    response
+}
+
+fn read_args(config: &Config, day: Day) -> Vec<String> {
+    build_args(
+        config,
         "read",
         &[
             "--description-only".into(),
             "--puzzle-file".into(),
-            puzzle_path,
+            config.puzzle_path(day),
         ],
         day,
-    );
-
-    call_aoc_cli(&args)
+    )
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
-
-    let args = build_args(
+fn download_args(config: &Config, day: Day) -> Vec<String> {
+    build_args(
+        config,
         "download",
         &[
             "--overwrite".into(),
             "--input-file".into(),
-            input_path.to_string(),
+            config.input_path(day),
             "--puzzle-file".into(),
-            puzzle_path.to_string(),
+            config.puzzle_path(day),
         ],
         day,
-    );
-
-    let output = call_aoc_cli(&args)?;
-    println!("---");
-    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-    Ok(output)
+    )
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
+fn submit_args(config: &Config, day: Day, part: u8, result: &str) -> Vec<String> {
     // workaround: the argument order is inverted for submit.
-    let mut args = build_args("submit", &[], day);
+    let mut args = build_args(config, "submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
-}
-
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+    args
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
-}
-
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
-}
-
-fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
+fn build_args(config: &Config, command: &str, args: &[String], day: Day) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
-    if let Some(year) = get_year() {
+    if let Some(year) = config.year {
         cmd_args.push("--year".into());
         cmd_args.push(year.to_string());
     }
@@ -108,7 +172,7 @@ fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
     cmd_args
 }
 
-fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
+fn call_aoc_cli(args: &[String]) -> Result<String, AocCommandError> {
     // println!("Calling >aoc with: {}", args.join(" "));
     let output = Command::new("aoc")
         .args(args)
@@ -117,9 +181,233 @@ fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
         .output()
         .map_err(|_| AocCommandError::CommandNotCallable)?;
 
-    if output.status.success() {
-        Ok(output)
+    command_result(output.status.success(), &output.stdout)
+}
+
+/// The captured stdout of an aoc-cli call, kept on failure as it holds the response of the server.
+fn command_result(success: bool, stdout: &[u8]) -> Result<String, AocCommandError> {
+    let stdout = String::from_utf8_lossy(stdout).into_owned();
+
+    if success {
+        Ok(stdout)
     } else {
-        Err(AocCommandError::BadExitStatus(output))
+        Err(AocCommandError::BadExitStatus(stdout))
+    }
+}
+
+/// The session cookie, read from the same places as aoc-cli:
+/// the `ADVENT_OF_CODE_SESSION` environment variable or the `~/.adventofcode.session` file.
+pub fn session() -> Option<String> {
+    env::var("ADVENT_OF_CODE_SESSION")
+        .ok()
+        .or_else(|| {
+            let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?;
+            fs::read_to_string(format!("{home}/.adventofcode.session")).ok()
+        })
+        .map(|session| session.trim().to_string())
+        .filter(|session| !session.is_empty())
+}
+
+/// Fetches a page that aoc-cli does not offer and that is only served with the session cookie,
+/// e.g. `/2024/leaderboard/self`. Servers other than adventofcode.com, like the test server, may not need one.
+pub fn fetch_authenticated(config: &Config, path: &str) -> Result<String, String> {
+    let url = format!("{}{path}", config.server.url.trim_end_matches('/'));
+
+    let session = session();
+    if session.is_none() && config.server.url.trim_end_matches('/') == OFFICIAL_URL {
+        return Err(format!("{url} needs a session cookie, store it in \"~/.adventofcode.session\" or the ADVENT_OF_CODE_SESSION environment variable."));
+    }
+
+    let response = http::get(&url, session.as_deref())?;
+    if !response.is_success() {
+        return Err(format!("{url} responded with status {}.", response.status));
+    }
+    Ok(response.body)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        check, command_result, download, download_args, read, read_args, submit, submit_answer,
+        submit_args, AocCommandError,
+    };
+    use crate::day;
+    use crate::template::progress::SubmissionOutcome;
+    use crate::template::puzzle::Puzzle;
+    use crate::template::submission::read_log;
+    use crate::template::test_server::{fake_aoc_cli, TestServer, ANSWER, INPUT};
+    use crate::template::Config;
+    use std::{fs, path::Path, sync::MutexGuard};
+
+    /// A configuration that writes to the directory `dir`.
+    fn config_in(dir: &Path) -> Config {
+        let mut config = Config {
+            year: Some(2024),
+            ..Config::default()
+        };
+        config.paths.inputs = dir.to_string_lossy().into();
+        config.paths.puzzles = dir.to_string_lossy().into();
+        config.paths.submissions = dir.join("submissions.jsonl").to_string_lossy().into();
+        config
+    }
+
+    /// A configuration in a temporary directory, with the fake aoc-cli talking to a fresh test server.
+    fn setup(name: &str) -> (TestServer, Config, MutexGuard<'static, ()>) {
+        let (server, dir) = TestServer::with_temp_dir(&format!("aoc_cli_{name}"));
+        let aoc_cli = fake_aoc_cli(&server.url);
+        (server, config_in(&dir), aoc_cli)
+    }
+
+    #[test]
+    fn builds_aoc_cli_arguments() {
+        let mut config = Config::default();
+        config.paths.inputs = "in".into();
+        config.paths.puzzles = "puzzles".into();
+
+        assert_eq!(
+            read_args(&config, day!(1)),
+            [
+                "--description-only",
+                "--puzzle-file",
+                "puzzles/01.md",
+                "--day",
+                "01",
+                "read"
+            ]
+        );
+
+        config.year = Some(2024);
+        assert_eq!(
+            download_args(&config, day!(12)),
+            [
+                "--overwrite",
+                "--input-file",
+                "in/12.txt",
+                "--puzzle-file",
+                "puzzles/12.md",
+                "--year",
+                "2024",
+                "--day",
+                "12",
+                "download"
+            ]
+        );
+        assert_eq!(
+            submit_args(&config, day!(3), 2, "42"),
+            ["--year", "2024", "--day", "03", "submit", "2", "42"]
+        );
+    }
+
+    #[test]
+    fn parses_aoc_cli_output() {
+        let response = "That's not the right answer; your answer is too low.\n";
+        assert_eq!(command_result(true, response.as_bytes()).unwrap(), response);
+
+        // a failed call keeps its output, so that the response of a rejected submission is still logged.
+        match command_result(false, response.as_bytes()) {
+            Err(AocCommandError::BadExitStatus(output)) => {
+                assert_eq!(
+                    SubmissionOutcome::parse(&output),
+                    SubmissionOutcome::Incorrect
+                );
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn checks_for_aoc_cli() {
+        let (_server, _config, _aoc_cli) = setup("check");
+        assert!(check().is_ok());
+    }
+
+    #[test]
+    fn downloads_input_and_puzzle() {
+        let (server, config, _aoc_cli) = setup("download");
+        download(&config, day!(1)).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(config.input_path(day!(1))).unwrap(),
+            INPUT
+        );
+
+        let puzzle = Puzzle::read(&config, day!(1)).unwrap();
+        assert_eq!(puzzle.title.as_deref(), Some("Test & Sum"));
+        assert_eq!(puzzle.examples, vec!["1\n2\n3\n".to_string()]);
+        assert_eq!(puzzle.answers[0].as_deref(), Some("6"));
+
+        assert_eq!(
+            server.requests(),
+            vec!["GET /2024/day/1/input", "GET /2024/day/1"]
+        );
+    }
+
+    #[test]
+    fn reads_puzzle() {
+        let (_server, config, _aoc_cli) = setup("read");
+        read(&config, day!(2)).unwrap();
+
+        let puzzle = Puzzle::read(&config, day!(2)).unwrap();
+        assert_eq!(puzzle.title.as_deref(), Some("Test & Sum"));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (server, config, _aoc_cli) = setup("submit");
+
+        // every wrong answer makes the next submission hit the rate limit of the server.
+        // the answers are submitted without the log, which would refuse to submit during the cooldown.
+        let answer = ANSWER.to_string();
+        let verdicts: Vec<(String, SubmissionOutcome)> = ["100", "1", "1", &answer, &answer]
+            .iter()
+            .map(|answer| {
+                let response = submit_answer(&config, day!(3), 1, answer).unwrap();
+                let outcome = SubmissionOutcome::parse(&response);
+                (response, outcome)
+            })
+            .collect();
+
+        assert!(verdicts[0].0.contains("your answer is too high"));
+        assert_eq!(verdicts[0].1, SubmissionOutcome::Incorrect);
+        assert!(verdicts[1].0.contains("You gave an answer too recently"));
+        assert_eq!(verdicts[1].1, SubmissionOutcome::Unchecked);
+        assert!(verdicts[2].0.contains("your answer is too low"));
+        assert_eq!(verdicts[3].1, SubmissionOutcome::Unchecked);
+        assert_eq!(verdicts[4].1, SubmissionOutcome::Correct);
+
+        assert_eq!(
+            server.requests()[0],
+            "POST /2024/day/3/answer level=1&answer=100"
+        );
+    }
+
+    #[test]
+    fn refuses_submissions_during_cooldown() {
+        let (server, config, _aoc_cli) = setup("cooldown");
+
+        submit(&config, day!(4), 2, "100").unwrap();
+        let error = submit(&config, day!(4), 2, "1").unwrap_err();
+        assert!(matches!(error, AocCommandError::Cooldown(55..=60)));
+        assert_eq!(server.requests().len(), 1);
+
+        let log = read_log(&config.paths.submissions);
+        assert_eq!(log.len(), 1);
+        assert_eq!(
+            (log[0].day, log[0].part, log[0].answer.as_str()),
+            (day!(4), 2, "100")
+        );
+        assert!(log[0].response.contains("too high"));
+    }
+
+    #[test]
+    fn reports_server_errors() {
+        let (server, dir) = TestServer::with_temp_dir("aoc_cli_errors");
+        let _aoc_cli = fake_aoc_cli(&format!("{}/missing", server.url));
+        assert!(matches!(
+            download(&config_in(&dir), day!(1)),
+            Err(AocCommandError::BadExitStatus(_))
+        ));
     }
 }
@@ -0,0 +1,731 @@
+/// Describes the commands and options of the template CLI.
+/// Used to print help, suggest corrections for typos, validate option combinations and generate shell completions.
+use std::{fmt::Write as _, str::FromStr};
+
+/// The value an option takes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Value {
+    None,
+    Required(&'static str),
+    Optional(&'static str),
+}
+
+pub struct Flag {
+    pub name: &'static str,
+    pub value: Value,
+    pub help: &'static str,
+}
+
+pub struct Command {
+    pub name: &'static str,
+    /// the cargo alias that runs the command, see `.cargo/config.toml`.
+    pub alias: &'static str,
+    /// positional arguments, e.g. `<day>`.
+    pub args: &'static str,
+    pub about: &'static str,
+    pub flags: &'static [Flag],
+    /// pairs of options that can not be combined, with the reason.
+    pub conflicts: &'static [(&'static str, &'static str, &'static str)],
+    /// options that require another option, with the reason.
+    pub requires: &'static [(&'static str, &'static str, &'static str)],
+}
+
+const fn flag(name: &'static str, help: &'static str) -> Flag {
+    Flag {
+        name,
+        value: Value::None,
+        help,
+    }
+}
+
+const fn option(name: &'static str, value: &'static str, help: &'static str) -> Flag {
+    Flag {
+        name,
+        value: Value::Required(value),
+        help,
+    }
+}
+
+const RELEASE: Flag = flag("--release", "Run an optimized build");
+const DHAT: Flag = flag("--dhat", "Profile heap allocations with DHAT");
+const COUNT_ALLOCS: Flag = flag("--count-allocs", "Count allocations and allocated bytes");
+const COST: Flag = flag("--cost", "Measure instruction or iteration counts");
+
+/// Options that every command accepts.
+pub const GLOBAL_FLAGS: &[Flag] = &[
+    option(
+        "--config",
+        "<path>",
+        "Read the configuration from this file",
+    ),
+    option("--year", "<year>", "Override the configured year"),
+    option("--set", "<key>=<value>", "Override a configuration value"),
+    flag("--help", "Print help"),
+];
+
+pub const COMMANDS: &[Command] = &[
+    Command {
+        name: "scaffold",
+        alias: "scaffold",
+        args: "<day>",
+        about: "Create the solution, input and example files for a day",
+        flags: &[
+            flag("--download", "Download the input and puzzle afterwards"),
+            flag(
+                "--overwrite",
+                "Replace an existing solution file, keeping a backup",
+            ),
+            flag("--dry-run", "Print the files that would be created"),
+            option("--template", "<name>", "Template of the solution file"),
+        ],
+        conflicts: &[],
+        requires: &[],
+    },
+    Command {
+        name: "new-day",
+        alias: "new-day",
+        args: "<day>",
+        about: "Scaffold, download, extract the example and run the tests",
+        flags: &[option(
+            "--template",
+            "<name>",
+            "Template of the solution file",
+        )],
+        conflicts: &[],
+        requires: &[],
+    },
+    Command {
+        name: "download",
+        alias: "download",
+        args: "<day>",
+        about: "Download the input and puzzle description for a day",
+        flags: &[],
+        conflicts: &[],
+        requires: &[],
+    },
+    Command {
+        name: "read",
+        alias: "read",
+        args: "<day>",
+        about: "Print the puzzle description for a day",
+        flags: &[],
+        conflicts: &[],
+        requires: &[],
+    },
+    Command {
+        name: "solve",
+        alias: "solve",
+        args: "<day>",
+        about: "Run the solution for a day",
+        flags: &[
+            RELEASE,
+            option("--submit", "<part>", "Submit the answer of a part"),
+            flag("--yes", "Submit without asking for confirmation"),
+            flag("--watch", "Re-run tests and solution when files change"),
+            option(
+                "--input",
+                "<path>",
+                "Read the input from a file, `-` reads stdin",
+            ),
+            Flag {
+                name: "--example",
+                value: Value::Optional("[name]"),
+                help: "Run against the (named) example",
+            },
+            DHAT,
+            COUNT_ALLOCS,
+            flag("--profile-cpu", "Write a flamegraph per part"),
+            COST,
+            flag("--visualize", "Render grid simulations in the terminal"),
+            option(
+                "--export",
+                "<gif|png>",
+                "Write grid simulations to the visualizations directory",
+            ),
+            flag("--verbose", "Print the debug output of the solution"),
+        ],
+        conflicts: &[
+            (
+                "--dhat",
+                "--release",
+                "DHAT builds with its own `dhat` profile",
+            ),
+            (
+                "--dhat",
+                "--count-allocs",
+                "DHAT already reports allocations",
+            ),
+            (
+                "--dhat",
+                "--profile-cpu",
+                "only one profiler can run at a time",
+            ),
+            (
+                "--profile-cpu",
+                "--release",
+                "profiling builds with its own `profiling` profile",
+            ),
+            ("--input", "--example", "only one input can be used"),
+            ("--submit", "--watch", "watch mode does not submit answers"),
+            (
+                "--submit",
+                "--input",
+                "only answers for the puzzle input are submitted",
+            ),
+            (
+                "--submit",
+                "--example",
+                "only answers for the puzzle input are submitted",
+            ),
+            (
+                "--watch",
+                "--input",
+                "watch mode runs against the puzzle input",
+            ),
+            (
+                "--watch",
+                "--example",
+                "watch mode runs against the puzzle input",
+            ),
+        ],
+        requires: &[
+            (
+                "--submit",
+                "--release",
+                "answers should be computed by an optimized build",
+            ),
+            ("--yes", "--submit", "only submissions ask for confirmation"),
+        ],
+    },
+    Command {
+        name: "verify",
+        alias: "verify",
+        args: "<day>",
+        about: "Check a solution against inputs with known answers",
+        flags: &[
+            option("--dir", "<path>", "Directory of the inputs"),
+            RELEASE,
+        ],
+        conflicts: &[],
+        requires: &[],
+    },
+    Command {
+        name: "all",
+        alias: "all",
+        args: "",
+        about: "Run all solutions",
+        flags: &[RELEASE],
+        conflicts: &[],
+        requires: &[],
+    },
+    Command {
+        name: "time",
+        alias: "time",
+        args: "[day]",
+        about: "Benchmark solutions",
+        flags: &[
+            flag("--all", "Benchmark all solutions, including stored ones"),
+            flag("--store", "Store the timings and update the readme"),
+            DHAT,
+            COUNT_ALLOCS,
+            COST,
+        ],
+        conflicts: &[(
+            "--dhat",
+            "--count-allocs",
+            "DHAT already reports allocations",
+        )],
+        requires: &[],
+    },
+    Command {
+        name: "report",
+        alias: "bench-report",
+        args: "",
+        about: "Render the stored timings to an HTML report",
+        flags: &[option(
+            "--output",
+            "<path>",
+            "Write the report to this file",
+        )],
+        conflicts: &[],
+        requires: &[],
+    },
+    Command {
+        name: "status",
+        alias: "status",
+        args: "",
+        about: "Show the progress calendar",
+        flags: &[flag("--readme", "Update the calendar in the readme")],
+        conflicts: &[],
+        requires: &[],
+    },
+    Command {
+        name: "leaderboard",
+        alias: "leaderboard",
+        args: "<id>",
+        about: "Show a private leaderboard",
+        flags: &[option(
+            "--day",
+            "<day>",
+            "Show completion times for a day, defaults to the latest",
+        )],
+        conflicts: &[],
+        requires: &[],
+    },
+    Command {
+        name: "stats",
+        alias: "stats",
+        args: "",
+        about: "Import personal ranks and solve times",
+        flags: &[
+            option(
+                "--file",
+                "<path>",
+                "Read a saved copy of the stats page instead of downloading it",
+            ),
+            flag("--readme", "Update the benchmark tables in the readme"),
+        ],
+        conflicts: &[],
+        requires: &[],
+    },
+    Command {
+        name: "submissions",
+        alias: "submissions",
+        args: "[day]",
+        about: "List submitted answers and running cooldowns",
+        flags: &[],
+        conflicts: &[],
+        requires: &[],
+    },
+    Command {
+        name: "today",
+        alias: "today",
+        args: "",
+        about: "Scaffold, download and read the current day",
+        flags: &[flag("--wait", "Wait for the next puzzle to unlock first")],
+        conflicts: &[],
+        requires: &[],
+    },
+    Command {
+        name: "completions",
+        alias: "completions",
+        args: "<shell>",
+        about: "Print a completion script for bash, zsh or fish",
+        flags: &[],
+        conflicts: &[],
+        requires: &[],
+    },
+];
+
+pub fn find(name: &str) -> Option<&'static Command> {
+    COMMANDS.iter().find(|command| command.name == name)
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn usage(flag: &Flag) -> String {
+    match flag.value {
+        Value::None => flag.name.into(),
+        Value::Required(value) | Value::Optional(value) => format!("{} {value}", flag.name),
+    }
+}
+
+/// Write sections of `(left, right)` rows, aligning the right column across all sections.
+fn write_sections(out: &mut String, sections: &[(&str, Vec<(String, &str)>)]) {
+    let width = sections
+        .iter()
+        .flat_map(|(_, rows)| rows.iter().map(|(left, _)| left.len()))
+        .max()
+        .unwrap_or(0);
+
+    for (title, rows) in sections {
+        if rows.is_empty() {
+            continue;
+        }
+
+        let _ = writeln!(out, "\n{title}:");
+        for (left, right) in rows {
+            let _ = writeln!(out, "  {left:<width$}  {right}");
+        }
+    }
+}
+
+fn flag_rows(flags: &[Flag]) -> Vec<(String, &str)> {
+    flags.iter().map(|flag| (usage(flag), flag.help)).collect()
+}
+
+/// Help for the CLI, listing all commands.
+pub fn help() -> String {
+    let mut out = String::from("Usage: cargo <command> [options]\n");
+
+    let commands = COMMANDS
+        .iter()
+        .map(|command| {
+            let usage = format!("{} {}", command.alias, command.args);
+            (usage.trim_end().to_string(), command.about)
+        })
+        .collect();
+
+    write_sections(
+        &mut out,
+        &[
+            ("Commands", commands),
+            ("Global options", flag_rows(GLOBAL_FLAGS)),
+        ],
+    );
+
+    out.push_str("\nRun `cargo <command> --help` for the options of a command.\n");
+    out
+}
+
+/// Help for a single command, listing its options.
+pub fn command_help(command: &Command) -> String {
+    let usage = format!("cargo {} {} [options]", command.alias, command.args);
+    let mut out = format!("{}\n\nUsage: {}\n", command.about, usage.replace("  ", " "));
+
+    write_sections(
+        &mut out,
+        &[
+            ("Options", flag_rows(command.flags)),
+            ("Global options", flag_rows(GLOBAL_FLAGS)),
+        ],
+    );
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+
+        for (j, cb) in b.iter().enumerate() {
+            let current = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                previous
+            } else {
+                1 + previous.min(row[j]).min(current)
+            };
+            previous = current;
+        }
+    }
+
+    row[b.len()]
+}
+
+/// The closest candidate to a misspelled `name`, if any is close enough.
+pub fn suggest<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, candidate)| *distance <= 2.max(candidate.len() / 3))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+fn did_you_mean(suggestion: Option<&str>) -> String {
+    suggestion.map_or_else(String::new, |x| format!(" Did you mean `{x}`?"))
+}
+
+/// Error message for an unknown command.
+pub fn unknown_command(name: &str) -> String {
+    let suggestion = suggest(name, COMMANDS.iter().map(|command| command.name));
+    format!(
+        "unknown command `{name}`.{} Run `cargo run -- --help` for a list of commands.",
+        did_you_mean(suggestion)
+    )
+}
+
+/// Check the options passed to a command: every option must be known,
+/// and the combination of options must be allowed.
+pub fn validate(command: &Command, args: &[String]) -> Result<(), String> {
+    let known = || command.flags.iter().chain(GLOBAL_FLAGS);
+    let mut present = vec![];
+    let mut index = 0;
+
+    while index < args.len() {
+        let arg = args[index].as_str();
+        index += 1;
+
+        if !arg.starts_with("--") {
+            continue;
+        }
+
+        // options may also be passed as `--name=value`.
+        let name = arg.split_once('=').map_or(arg, |(name, _)| name);
+
+        let Some(flag) = known().find(|flag| flag.name == name) else {
+            let suggestion = suggest(name, known().map(|flag| flag.name));
+            return Err(format!(
+                "unknown option `{name}` for `{}`.{} Run `cargo {} --help` for a list of options.",
+                command.name,
+                did_you_mean(suggestion),
+                command.alias
+            ));
+        };
+
+        if name == arg {
+            match flag.value {
+                Value::None => {}
+                Value::Required(value) => {
+                    if args.get(index).is_none() {
+                        return Err(format!("option `{name}` expects a value: {value}."));
+                    }
+                    index += 1;
+                }
+                Value::Optional(_) => {
+                    if args.get(index).is_some_and(|x| !x.starts_with("--")) {
+                        index += 1;
+                    }
+                }
+            }
+        }
+
+        present.push(name);
+    }
+
+    for (a, b, reason) in command.conflicts {
+        if present.contains(a) && present.contains(b) {
+            return Err(format!("`{a}` can not be combined with `{b}`: {reason}."));
+        }
+    }
+
+    for (a, b, reason) in command.requires {
+        if present.contains(a) && !present.contains(b) {
+            return Err(format!("`{a}` requires `{b}`: {reason}."));
+        }
+    }
+
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl FromStr for Shell {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            _ => Err(format!(
+                "unsupported shell `{s}`, expected one of: bash, zsh, fish."
+            )),
+        }
+    }
+}
+
+/// The options of a command, including the global ones.
+fn all_flags(command: &Command) -> impl Iterator<Item = &Flag> {
+    command.flags.iter().chain(GLOBAL_FLAGS)
+}
+
+/// A completion script for the cargo aliases of the template.
+/// The scripts complete the options of the template commands and defer to the regular cargo completion otherwise.
+pub fn completions(shell: Shell) -> String {
+    match shell {
+        Shell::Bash => bash_completions(),
+        Shell::Zsh => zsh_completions(),
+        Shell::Fish => fish_completions(),
+    }
+}
+
+fn bash_completions() -> String {
+    let mut out = String::from(
+        "# bash completion for the advent-of-code template, load with:\n\
+         # source <(cargo completions bash)\n\
+         _advent_of_code() {\n\
+         \x20   local cur=\"${COMP_WORDS[COMP_CWORD]}\" prev=\"${COMP_WORDS[COMP_CWORD-1]}\" opts\n\
+         \x20   if [[ $COMP_CWORD -gt 1 ]]; then\n\
+         \x20       case \"${COMP_WORDS[1]}\" in\n",
+    );
+
+    for command in COMMANDS {
+        let opts: Vec<&str> = all_flags(command).map(|flag| flag.name).collect();
+        let values: Vec<&str> = all_flags(command)
+            .filter(|flag| matches!(flag.value, Value::Required(_)))
+            .map(|flag| flag.name)
+            .collect();
+
+        let _ = writeln!(out, "            {})", command.alias);
+        let _ = writeln!(
+            out,
+            "                [[ \"$prev\" == @({}) ]] && return",
+            values.join("|")
+        );
+        let _ = writeln!(out, "                opts=\"{}\"", opts.join(" "));
+        let _ = writeln!(
+            out,
+            "                COMPREPLY=($(compgen -W \"$opts\" -- \"$cur\"))"
+        );
+        out.push_str("                return ;;\n");
+    }
+
+    out.push_str(
+        "        esac\n\
+         \x20   fi\n\
+         \x20   declare -F _cargo >/dev/null && _cargo \"$@\"\n\
+         }\n\
+         shopt -s extglob\n\
+         complete -o default -F _advent_of_code cargo\n",
+    );
+    out
+}
+
+fn zsh_completions() -> String {
+    let mut out = String::from(
+        "#compdef cargo\n\
+         # zsh completion for the advent-of-code template, load with:\n\
+         # source <(cargo completions zsh)\n\
+         _advent_of_code() {\n\
+         \x20   local -a opts\n\
+         \x20   case \"${words[2]}\" in\n",
+    );
+
+    for command in COMMANDS {
+        let opts: Vec<String> = all_flags(command)
+            .map(|flag| {
+                let value = match flag.value {
+                    Value::Required(value) => format!(":{}:_files", value.trim_matches(['<', '>'])),
+                    Value::None | Value::Optional(_) => String::new(),
+                };
+                format!("'{}[{}]{value}'", flag.name, flag.help.replace('\'', ""))
+            })
+            .collect();
+
+        let _ = writeln!(out, "        {})", command.alias);
+        let _ = writeln!(out, "            opts=({})", opts.join(" "));
+        out.push_str("            _arguments -s $opts '*::argument:_files'\n");
+        out.push_str("            ;;\n");
+    }
+
+    out.push_str(
+        "        *)\n\
+         \x20           (( $+functions[_cargo] )) || autoload -Uz _cargo\n\
+         \x20           _cargo \"$@\"\n\
+         \x20           ;;\n\
+         \x20   esac\n\
+         }\n\
+         compdef _advent_of_code cargo\n",
+    );
+    out
+}
+
+fn fish_completions() -> String {
+    let mut out = String::from(
+        "# fish completion for the advent-of-code template, load with:\n\
+         # cargo completions fish | source\n",
+    );
+
+    for command in COMMANDS {
+        let _ = writeln!(
+            out,
+            "complete -c cargo -n '__fish_use_subcommand' -a {} -d '{}'",
+            command.alias,
+            command.about.replace('\'', "")
+        );
+
+        for flag in all_flags(command) {
+            let value = match flag.value {
+                Value::Required(_) => " -r",
+                Value::None | Value::Optional(_) => "",
+            };
+            let _ = writeln!(
+                out,
+                "complete -c cargo -n '__fish_seen_subcommand_from {}' -l {}{value} -d '{}'",
+                command.alias,
+                flag.name.trim_start_matches("--"),
+                flag.help.replace('\'', "")
+            );
+        }
+    }
+
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{command_help, completions, find, help, suggest, validate, Shell, COMMANDS};
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn suggests_close_matches() {
+        let commands = || COMMANDS.iter().map(|command| command.name);
+        assert_eq!(suggest("solv", commands()), Some("solve"));
+        assert_eq!(suggest("scafold", commands()), Some("scaffold"));
+        assert_eq!(suggest("tiem", commands()), Some("time"));
+        assert_eq!(suggest("xyz", commands()), None);
+    }
+
+    #[test]
+    fn validates_options() {
+        let solve = find("solve").unwrap();
+        assert!(validate(solve, &args("01 --release --submit 1")).is_ok());
+        assert!(validate(solve, &args("01 --example 2 --time")).is_err());
+        assert!(validate(solve, &args("01 --example --release --year 2023")).is_ok());
+        assert!(validate(solve, &args("01 --input=foo.txt")).is_ok());
+
+        let error = validate(solve, &args("01 --relase")).unwrap_err();
+        assert!(error.contains("Did you mean `--release`?"));
+
+        let error = validate(solve, &args("01 --dhat --release")).unwrap_err();
+        assert!(error.contains("`--dhat` can not be combined with `--release`"));
+
+        let error = validate(solve, &args("01 --submit 1")).unwrap_err();
+        assert!(error.contains("`--submit` requires `--release`"));
+
+        let error = validate(solve, &args("01 --input")).unwrap_err();
+        assert!(error.contains("expects a value"));
+
+        // values are not mistaken for options.
+        assert!(validate(find("report").unwrap(), &args("--output --x")).is_ok());
+    }
+
+    #[test]
+    fn prints_help() {
+        let help = help();
+        assert!(help.contains("  bench-report"));
+        assert!(help.contains("--set <key>=<value>"));
+
+        let help = command_help(find("solve").unwrap());
+        assert!(
+            help.starts_with("Run the solution for a day\n\nUsage: cargo solve <day> [options]")
+        );
+        assert!(help.contains("--example [name]"));
+
+        let help = command_help(find("all").unwrap());
+        assert!(help.contains("Usage: cargo all [options]"));
+    }
+
+    #[test]
+    fn generates_completions() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+            let script = completions(shell);
+            for command in COMMANDS {
+                assert!(script.contains(command.alias));
+            }
+            assert!(script.contains("profile-cpu"));
+        }
+
+        assert!(completions(Shell::Bash).contains("complete -o default -F _advent_of_code cargo"));
+        assert!(completions(Shell::Fish)
+            .contains("complete -c cargo -n '__fish_seen_subcommand_from solve' -l submit -r"));
+    }
+}
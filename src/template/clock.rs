@@ -0,0 +1,27 @@
+/// Access to the current time, so that code waiting for a puzzle to unlock can be tested without waiting.
+use std::{
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+pub trait Clock {
+    /// Seconds since the unix epoch.
+    fn now(&self) -> i64;
+    fn sleep(&self, duration: Duration);
+}
+
+/// The clock of the operating system.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> i64 {
+        let elapsed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time is before the unix epoch");
+        i64::try_from(elapsed.as_secs()).unwrap_or(i64::MAX)
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
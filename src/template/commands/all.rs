@@ -1,5 +1,10 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::run_multi::{run_multi, RunOptions};
+use crate::template::Config;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(config: &Config, is_release: bool) {
+    let options = RunOptions {
+        release: is_release,
+        ..RunOptions::default()
+    };
+    run_multi(config, &config.all_days().collect(), options, false);
 }
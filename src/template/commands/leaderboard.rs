@@ -0,0 +1,179 @@
+use std::process;
+
+use crate::template::leaderboard::{self, Leaderboard, Member};
+use crate::template::{
+    format_duration, latest_event, unlock_time, AllDays, Clock, Config, Day, SystemClock,
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+
+/// Star of a day: `★` for both parts, `☆` for the first part, `·` for none.
+fn star(member: &Member, day: Day) -> String {
+    match member.completions.get(&day) {
+        Some([_, Some(_)]) => format!("{ANSI_BOLD}★{ANSI_RESET}"),
+        Some([Some(_), None]) => "☆".into(),
+        _ => "·".into(),
+    }
+}
+
+/// Stars of the first `days` days by member.
+fn render_table(leaderboard: &Leaderboard, days: u8) -> Vec<String> {
+    let days: Vec<Day> = AllDays::until(days).collect();
+    let indent = " ".repeat(4 + 2 + 5 + 2);
+
+    let tens: String = days
+        .iter()
+        .map(|day| match day.into_inner() / 10 {
+            0 => ' ',
+            tens => char::from(b'0' + tens),
+        })
+        .collect();
+    let units: String = days
+        .iter()
+        .map(|day| char::from(b'0' + day.into_inner() % 10))
+        .collect();
+
+    let mut lines = vec![
+        format!("{indent}{tens}").trim_end().to_string(),
+        format!(
+            "{ANSI_BOLD}{:>4}  {:>5}  {units}  Name{ANSI_RESET}",
+            "#", "Score"
+        ),
+    ];
+
+    for (index, member) in leaderboard.members.iter().enumerate() {
+        let stars: String = days.iter().map(|day| star(member, *day)).collect();
+        lines.push(format!(
+            "{:>4}  {:>5}  {stars}  {}",
+            format!("{})", index + 1),
+            member.local_score,
+            member.display_name()
+        ));
+    }
+
+    lines
+}
+
+/// Time from the unlock of a puzzle to a star, e.g. `00:12:34`.
+fn completion_time(star: Option<u64>, unlock: i64) -> String {
+    star.map_or_else(
+        || "-".into(),
+        |star| format_duration(i64::try_from(star).unwrap_or(0) - unlock),
+    )
+}
+
+fn render_times(leaderboard: &Leaderboard, day: Day, unlock: i64) -> Vec<String> {
+    let mut members: Vec<(&Member, [Option<u64>; 2])> = leaderboard
+        .members
+        .iter()
+        .filter_map(|member| member.completions.get(&day).map(|stars| (member, *stars)))
+        .collect();
+
+    // fastest second star first, then fastest first star.
+    members.sort_by_key(|(_, [one, two])| (two.is_none(), *two, *one));
+
+    let mut lines = vec![format!(
+        "{ANSI_BOLD}{:<12}  {:<12}  Name{ANSI_RESET}",
+        "Part 1", "Part 2"
+    )];
+
+    if members.is_empty() {
+        lines.push(format!("{ANSI_ITALIC}No stars yet.{ANSI_RESET}"));
+    }
+
+    for (member, [one, two]) in members {
+        lines.push(format!(
+            "{:<12}  {:<12}  {}",
+            completion_time(one, unlock),
+            completion_time(two, unlock),
+            member.display_name()
+        ));
+    }
+
+    lines
+}
+
+/// The latest day anyone earned a star on.
+fn latest_day(leaderboard: &Leaderboard) -> Option<Day> {
+    leaderboard
+        .members
+        .iter()
+        .filter_map(|member| member.completions.keys().next_back())
+        .max()
+        .copied()
+}
+
+pub fn handle(config: &Config, id: &str, day: Option<Day>) {
+    let clock = SystemClock;
+    let year = config.year.map_or_else(
+        || latest_event(clock.now(), config.server.utc_offset),
+        i64::from,
+    );
+
+    let fetched = match leaderboard::fetch(config, year, id, &clock) {
+        Ok(fetched) => fetched,
+        Err(e) => {
+            eprintln!("Failed to fetch the leaderboard: {e}");
+            process::exit(1);
+        }
+    };
+
+    let age = u64::try_from(clock.now())
+        .unwrap_or(0)
+        .saturating_sub(fetched.timestamp);
+    let freshness = if fetched.is_stale {
+        format!("outdated, fetched {} minutes ago", age / 60)
+    } else if age < 60 {
+        "just fetched".into()
+    } else {
+        format!("fetched {} minutes ago", age / 60)
+    };
+
+    println!("{ANSI_BOLD}Private leaderboard {id} · {year}{ANSI_RESET} {ANSI_ITALIC}({freshness}){ANSI_RESET}");
+    println!();
+    for line in render_table(&fetched.leaderboard, config.max_day()) {
+        println!("{line}");
+    }
+
+    if let Some(day) = day.or_else(|| latest_day(&fetched.leaderboard)) {
+        println!();
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET} {ANSI_ITALIC}(time since unlock){ANSI_RESET}");
+        let unlock = unlock_time(year, day, config.server.utc_offset);
+        for line in render_times(&fetched.leaderboard, day, unlock) {
+            println!("{line}");
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{latest_day, render_table, render_times};
+    use crate::day;
+    use crate::template::leaderboard::Leaderboard;
+    use crate::template::test_server::LEADERBOARD;
+    use crate::template::{unlock_time, ANSI_BOLD, ANSI_RESET};
+
+    #[test]
+    fn renders_leaderboard() {
+        let leaderboard = Leaderboard::try_from(LEADERBOARD).unwrap();
+        let lines = render_table(&leaderboard, 25);
+        let gold = format!("{ANSI_BOLD}★{ANSI_RESET}");
+
+        assert!(lines[0].trim_start().starts_with("111111111122222"));
+        assert!(lines[2].starts_with(&format!("  1)      6  {gold}☆·")));
+        assert!(lines[2].ends_with("  Alice"));
+        assert!(lines[4].starts_with("  3)      1  ·☆·"));
+    }
+
+    #[test]
+    fn renders_completion_times() {
+        let leaderboard = Leaderboard::try_from(LEADERBOARD).unwrap();
+        assert_eq!(latest_day(&leaderboard), Some(day!(2)));
+
+        let lines = render_times(&leaderboard, day!(1), unlock_time(2024, day!(1), -5));
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with("00:01:01      00:05:00      Alice"));
+        assert!(lines[2].ends_with("(anonymous user #3)"));
+    }
+}
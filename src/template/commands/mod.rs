@@ -1,6 +1,15 @@
 pub mod all;
 pub mod download;
+pub mod leaderboard;
+pub mod new_day;
 pub mod read;
+pub mod report;
 pub mod scaffold;
 pub mod solve;
+pub mod stats;
+pub mod status;
+pub mod submissions;
 pub mod time;
+pub mod today;
+pub mod verify;
+pub mod watch;
@@ -0,0 +1,157 @@
+/// Sets up a day in one go: scaffold, download, extract the example and run the tests once.
+/// Every step checks what is already in place, so running the command again resumes where it failed.
+use std::{
+    fs,
+    path::Path,
+    process::{self, Command, Stdio},
+};
+
+use crate::template::commands::scaffold::{self, Action, ScaffoldOptions};
+use crate::template::puzzle::Puzzle;
+use crate::template::{aoc_cli, Config, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+enum Outcome {
+    Done(String),
+    Skipped(String),
+    Failed(String),
+}
+
+fn is_empty(path: &str) -> bool {
+    fs::read_to_string(path).map_or(true, |contents| contents.trim().is_empty())
+}
+
+fn scaffold_step(config: &Config, day: Day, template: &str) -> Outcome {
+    let options = ScaffoldOptions {
+        template: template.into(),
+        ..ScaffoldOptions::default()
+    };
+
+    match scaffold::create_files(config, day, &options) {
+        Ok(actions) if actions.iter().all(|action| *action == Action::Skip) => {
+            Outcome::Skipped("all files exist".into())
+        }
+        Ok(_) => Outcome::Done("created missing files".into()),
+        Err(e) => Outcome::Failed(e),
+    }
+}
+
+fn download_step(config: &Config, day: Day) -> Outcome {
+    if !is_empty(&config.input_path(day)) && Path::new(&config.puzzle_path(day)).exists() {
+        return Outcome::Skipped("input and puzzle were downloaded".into());
+    }
+
+    if aoc_cli::check().is_err() {
+        return Outcome::Failed(
+            "command \"aoc\" not found. Try running \"cargo install aoc-cli\" to install it."
+                .into(),
+        );
+    }
+
+    match aoc_cli::download(config, day) {
+        Ok(_) => Outcome::Done("downloaded input and puzzle".into()),
+        Err(e) => Outcome::Failed(format!("failed to call aoc-cli: {e}")),
+    }
+}
+
+fn examples_step(config: &Config, day: Day, template: &str) -> Outcome {
+    let Some(puzzle) = Puzzle::read(config, day) else {
+        return Outcome::Failed("the puzzle has not been downloaded.".into());
+    };
+
+    let mut done = vec![];
+
+    let example_path = config.example_path(day);
+    if is_empty(&example_path) {
+        let Some(example) = puzzle.examples.first() else {
+            return Outcome::Failed(format!(
+                "found no example in the puzzle, paste it into \"{example_path}\"."
+            ));
+        };
+
+        if let Err(e) = fs::write(&example_path, example) {
+            return Outcome::Failed(format!("failed to write \"{example_path}\": {e}"));
+        }
+        done.push(format!("wrote example to \"{example_path}\""));
+    }
+
+    match scaffold::fill_puzzle_details(config, day, template) {
+        Ok(true) => done.push("filled in title and example answers".into()),
+        Ok(false) => {}
+        Err(e) => return Outcome::Failed(e),
+    }
+
+    if done.is_empty() {
+        Outcome::Skipped("example is in place".into())
+    } else {
+        Outcome::Done(done.join(", "))
+    }
+}
+
+fn test_step(config: &Config, day: Day) -> Outcome {
+    let status = Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &day.to_string()])
+        .envs(config.env_vars())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status();
+
+    match status {
+        // a fresh solution is expected to fail its tests.
+        Ok(status) if status.success() => Outcome::Done("tests pass".into()),
+        Ok(_) => Outcome::Done("tests fail, time to solve!".into()),
+        Err(e) => Outcome::Failed(format!("failed to run cargo test: {e}")),
+    }
+}
+
+pub fn handle(config: &Config, day: Day, template: &str) {
+    let steps: [(&str, &dyn Fn() -> Outcome); 4] = [
+        ("Scaffold", &|| scaffold_step(config, day, template)),
+        ("Download", &|| download_step(config, day)),
+        ("Examples", &|| examples_step(config, day, template)),
+        ("Test", &|| test_step(config, day)),
+    ];
+
+    let mut summary = vec![];
+    let mut failed = false;
+
+    for (index, (name, step)) in steps.iter().enumerate() {
+        if failed {
+            summary.push(format!("  - {name} {ANSI_ITALIC}(not run){ANSI_RESET}"));
+            continue;
+        }
+
+        println!(
+            "{ANSI_BOLD}[{}/{}] {name}{ANSI_RESET}",
+            index + 1,
+            steps.len()
+        );
+
+        let line = match step() {
+            Outcome::Done(detail) => format!("  ✔ {name}: {detail}"),
+            Outcome::Skipped(reason) => {
+                format!("  ✔ {name} {ANSI_ITALIC}(skipped, {reason}){ANSI_RESET}")
+            }
+            Outcome::Failed(e) => {
+                failed = true;
+                format!("  ✖ {name}: {e}")
+            }
+        };
+
+        summary.push(line);
+        println!();
+    }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    for line in &summary {
+        println!("{line}");
+    }
+
+    if failed {
+        println!();
+        println!("Fix the problem and run `cargo new-day {day}` again to resume.");
+        process::exit(1);
+    }
+
+    println!("---");
+    println!("🎄 Type `cargo solve {day}` to run your solution.");
+}
@@ -0,0 +1,409 @@
+/// Renders the stored timings and their history into a self-contained HTML page with SVG charts.
+use std::{fmt::Write as _, fs, process, time::Duration};
+
+use crate::template::history::History;
+use crate::template::timings::{parse_duration, Timing, Timings};
+use crate::template::{format_date, Config};
+
+const PART_COLORS: [&str; 2] = ["#4e79a7", "#f28e2b"];
+const CHART_WIDTH: f64 = 760.0;
+
+pub fn handle(config: &Config, output: Option<String>) {
+    let read = Timings::read_from_path(&config.paths.timings)
+        .and_then(|timings| Ok((timings, History::read_from_file(&config.paths.history)?)));
+    let (mut timings, mut history) = match read {
+        Ok(read) => read,
+        Err(e) => {
+            eprintln!("Failed to read stored timings: {e}");
+            process::exit(1);
+        }
+    };
+
+    // stored timings of days outside the event are kept in the file, but not reported.
+    let in_event = |timing: &Timing| config.check_day(timing.day).is_ok();
+    timings.data.retain(in_event);
+    for run in &mut history.runs {
+        run.timings.data.retain(in_event);
+    }
+
+    if timings.data.is_empty() && history.runs.is_empty() {
+        eprintln!("No timings stored yet. Run `cargo time --store` first.");
+        process::exit(1);
+    }
+
+    let path = output.unwrap_or_else(|| config.paths.report.clone());
+    let year = config.year.map(|year| year.to_string());
+
+    match fs::write(&path, render(&timings, &history, year.as_deref())) {
+        Ok(()) => println!("Wrote benchmark report to \"{path}\"."),
+        Err(e) => {
+            eprintln!("Failed to write report: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn render(timings: &Timings, history: &History, year: Option<&str>) -> String {
+    let title = year.map_or_else(
+        || "Advent of Code benchmarks".into(),
+        |year| format!("Advent of Code {year} benchmarks"),
+    );
+
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+  body {{ font-family: system-ui, sans-serif; margin: 2rem auto; max-width: 800px; color: #222; }}
+  h2 {{ margin-top: 2.5rem; }}
+  svg text {{ font-size: 11px; fill: #444; }}
+  .legend span {{ display: inline-block; width: 10px; height: 10px; margin: 0 4px 0 12px; }}
+  .trends {{ display: flex; flex-wrap: wrap; gap: 12px; }}
+  .trends figure {{ margin: 0; }}
+  .trends figcaption {{ font-size: 12px; font-weight: bold; }}
+</style>
+</head>
+<body>
+<h1>{title}</h1>
+<p>Total: <strong>{:.2}ms</strong> across {} days.</p>
+<p class="legend"><span style="background: {}"></span>Part 1<span style="background: {}"></span>Part 2</p>
+"#,
+        timings.total_millis(),
+        timings.data.len(),
+        PART_COLORS[0],
+        PART_COLORS[1],
+    );
+
+    html.push_str("<h2>Per day</h2>\n");
+    html.push_str(&bar_chart(timings));
+    html.push_str("<h2>Part 1 vs. part 2 (log scale)</h2>\n");
+    html.push_str(&log_chart(timings));
+    html.push_str("<h2>Trend across runs</h2>\n");
+    html.push_str(&trends(timings, history));
+    html.push_str("</body>\n</html>\n");
+
+    html
+}
+
+/// Durations of both parts in nanoseconds.
+fn part_nanos(timing: &Timing) -> [Option<f64>; 2] {
+    [
+        timing.part_1.as_deref().and_then(parse_duration),
+        timing.part_2.as_deref().and_then(parse_duration),
+    ]
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos as u64))
+}
+
+/// Horizontal bars with both parts stacked, on a linear scale.
+fn bar_chart(timings: &Timings) -> String {
+    const LABEL_WIDTH: f64 = 60.0;
+    const VALUE_WIDTH: f64 = 80.0;
+    const ROW_HEIGHT: f64 = 22.0;
+
+    let plot_width = CHART_WIDTH - LABEL_WIDTH - VALUE_WIDTH;
+    let max = timings
+        .data
+        .iter()
+        .map(|t| part_nanos(t).iter().flatten().sum::<f64>())
+        .fold(0_f64, f64::max)
+        .max(1.0);
+
+    #[allow(clippy::cast_precision_loss)]
+    let height = ROW_HEIGHT * timings.data.len() as f64;
+    let mut svg = format!(r#"<svg width="{CHART_WIDTH}" height="{height}">"#);
+
+    for (i, timing) in timings.data.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let y = ROW_HEIGHT * i as f64;
+        let mut x = LABEL_WIDTH;
+
+        let _ = write!(
+            svg,
+            r#"<text x="0" y="{}">Day {}</text>"#,
+            y + 15.0,
+            timing.day.into_inner()
+        );
+
+        for (part, nanos) in part_nanos(timing).into_iter().enumerate() {
+            let Some(nanos) = nanos else { continue };
+            let width = nanos / max * plot_width;
+            let _ = write!(
+                svg,
+                r#"<rect x="{x:.1}" y="{}" width="{width:.1}" height="{}" fill="{}"><title>Part {}: {}</title></rect>"#,
+                y + 3.0,
+                ROW_HEIGHT - 6.0,
+                PART_COLORS[part],
+                part + 1,
+                format_nanos(nanos)
+            );
+            x += width;
+        }
+
+        let total: f64 = part_nanos(timing).iter().flatten().sum();
+        let _ = write!(
+            svg,
+            r#"<text x="{:.1}" y="{}">{}</text>"#,
+            x + 6.0,
+            y + 15.0,
+            format_nanos(total)
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Position of `value` on a log scale between `min` and `max`, as a fraction from 0 to 1.
+fn log_scale(value: f64, min: f64, max: f64) -> f64 {
+    ((value.log10() - min.log10()) / (max.log10() - min.log10())).clamp(0.0, 1.0)
+}
+
+/// Vertical bars for both parts of each day, on a log scale.
+fn log_chart(timings: &Timings) -> String {
+    const AXIS_WIDTH: f64 = 60.0;
+    const PLOT_HEIGHT: f64 = 240.0;
+    const TOP: f64 = 10.0;
+    const BOTTOM: f64 = 20.0;
+
+    let values: Vec<f64> = timings
+        .data
+        .iter()
+        .flat_map(part_nanos)
+        .flatten()
+        .filter(|x| *x > 0.0)
+        .collect();
+
+    if values.is_empty() {
+        return "<p>No part timings stored.</p>\n".into();
+    }
+
+    // round the scale to powers of ten, so every gridline is a round duration.
+    let min = 10_f64.powf(
+        values
+            .iter()
+            .copied()
+            .fold(f64::MAX, f64::min)
+            .log10()
+            .floor(),
+    );
+    let mut max = 10_f64.powf(values.iter().copied().fold(0_f64, f64::max).log10().ceil());
+    if max <= min {
+        max = min * 10.0;
+    }
+
+    let bottom = TOP + PLOT_HEIGHT;
+    let y = |value: f64| bottom - log_scale(value, min, max) * PLOT_HEIGHT;
+
+    #[allow(clippy::cast_precision_loss)]
+    let group_width = (CHART_WIDTH - AXIS_WIDTH) / timings.data.len() as f64;
+    let bar_width = (group_width * 0.4).min(16.0);
+
+    let mut svg = format!(
+        r#"<svg width="{CHART_WIDTH}" height="{}">"#,
+        bottom + BOTTOM
+    );
+
+    let mut gridline = min;
+    while gridline <= max * 1.001 {
+        let _ = write!(
+            svg,
+            r##"<line x1="{AXIS_WIDTH}" x2="{CHART_WIDTH}" y1="{0:.1}" y2="{0:.1}" stroke="#ddd"/><text x="0" y="{1:.1}">{2}</text>"##,
+            y(gridline),
+            y(gridline) + 4.0,
+            format_nanos(gridline)
+        );
+        gridline *= 10.0;
+    }
+
+    for (i, timing) in timings.data.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let x = AXIS_WIDTH + group_width * i as f64 + (group_width - 2.0 * bar_width) / 2.0;
+
+        for (part, nanos) in part_nanos(timing).into_iter().enumerate() {
+            let Some(nanos) = nanos.filter(|x| *x > 0.0) else {
+                continue;
+            };
+
+            #[allow(clippy::cast_precision_loss)]
+            let _ = write!(
+                svg,
+                r#"<rect x="{:.1}" y="{:.1}" width="{bar_width:.1}" height="{:.1}" fill="{}"><title>Day {} part {}: {}</title></rect>"#,
+                x + bar_width * part as f64,
+                y(nanos),
+                bottom - y(nanos),
+                PART_COLORS[part],
+                timing.day.into_inner(),
+                part + 1,
+                format_nanos(nanos)
+            );
+        }
+
+        let _ = write!(
+            svg,
+            r#"<text x="{:.1}" y="{}" text-anchor="middle">{}</text>"#,
+            x + bar_width,
+            bottom + 14.0,
+            timing.day.into_inner()
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// One small line chart per day with the total duration of each stored run.
+fn trends(timings: &Timings, history: &History) -> String {
+    const WIDTH: f64 = 180.0;
+    const HEIGHT: f64 = 70.0;
+    const PADDING: f64 = 8.0;
+
+    if history.runs.is_empty() {
+        return "<p>No history yet. Every <code>cargo time --store</code> adds a run.</p>\n".into();
+    }
+
+    let mut days: Vec<_> = timings.data.iter().map(|t| t.day).collect();
+    for run in &history.runs {
+        days.extend(run.timings.data.iter().map(|t| t.day));
+    }
+    days.sort_unstable();
+    days.dedup();
+
+    let mut html = String::from(r#"<div class="trends">"#);
+
+    for day in days {
+        let runs = history.for_day(day);
+        if runs.is_empty() {
+            continue;
+        }
+
+        let min = runs
+            .iter()
+            .map(|(_, t)| t.total_nanos)
+            .fold(f64::MAX, f64::min);
+        let max = runs
+            .iter()
+            .map(|(_, t)| t.total_nanos)
+            .fold(0_f64, f64::max);
+        let range = (max - min).max(1.0);
+
+        #[allow(clippy::cast_precision_loss)]
+        let step = (WIDTH - 2.0 * PADDING) / (runs.len().max(2) - 1) as f64;
+
+        let points: Vec<(f64, f64, String)> = runs
+            .iter()
+            .enumerate()
+            .map(|(i, (timestamp, timing))| {
+                #[allow(clippy::cast_precision_loss)]
+                let x = PADDING + step * i as f64;
+                let y = HEIGHT
+                    - PADDING
+                    - (timing.total_nanos - min) / range * (HEIGHT - 2.0 * PADDING);
+                let label = format!(
+                    "{}: {}",
+                    format_date(*timestamp),
+                    format_nanos(timing.total_nanos)
+                );
+                (x, y, label)
+            })
+            .collect();
+
+        let polyline: Vec<String> = points
+            .iter()
+            .map(|(x, y, _)| format!("{x:.1},{y:.1}"))
+            .collect();
+
+        let _ = write!(
+            html,
+            r##"<figure><figcaption>Day {} &middot; {}</figcaption><svg width="{WIDTH}" height="{HEIGHT}"><polyline points="{}" fill="none" stroke="#59a14f" stroke-width="2"/>"##,
+            day.into_inner(),
+            format_nanos(runs[runs.len() - 1].1.total_nanos),
+            polyline.join(" ")
+        );
+
+        for (x, y, label) in points {
+            let _ = write!(
+                html,
+                r##"<circle cx="{x:.1}" cy="{y:.1}" r="3" fill="#59a14f"><title>{label}</title></circle>"##
+            );
+        }
+
+        html.push_str("</svg></figure>");
+    }
+
+    html.push_str("</div>\n");
+    html
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{log_scale, render};
+    use crate::day;
+    use crate::template::history::History;
+    use crate::template::timings::{Timing, Timings};
+
+    fn get_mock_timings() -> Timings {
+        let timing = |day, part_1: &str, part_2: &str, total_nanos| Timing {
+            day,
+            part_1: Some(part_1.into()),
+            part_2: Some(part_2.into()),
+            total_nanos,
+            part_1_heap: None,
+            part_2_heap: None,
+            part_1_allocs: None,
+            part_2_allocs: None,
+            part_1_cost: None,
+            part_2_cost: None,
+            parse: None,
+            part_1_samples: None,
+            part_2_samples: None,
+            part_1_stddev: None,
+            part_2_stddev: None,
+        };
+
+        Timings {
+            data: vec![
+                timing(day!(1), "10.0µs", "1.0ms", 1_010_000_f64),
+                timing(day!(2), "2.0s", "500.0ns", 2_000_000_500_f64),
+            ],
+        }
+    }
+
+    #[test]
+    fn scales_logarithmically() {
+        assert_eq!(log_scale(1.0, 1.0, 1000.0), 0.0);
+        assert!((log_scale(10.0, 1.0, 1000.0) - 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(log_scale(1000.0, 1.0, 1000.0), 1.0);
+        assert_eq!(log_scale(1e6, 1.0, 1000.0), 1.0);
+    }
+
+    #[test]
+    fn renders_report() {
+        let timings = get_mock_timings();
+        let mut history = History::default();
+        history.push(timings.clone());
+        history.push(timings.clone());
+
+        let html = render(&timings, &history, Some("2024"));
+
+        assert!(html.contains("<title>Advent of Code 2024 benchmarks</title>"));
+        assert_eq!(html.matches("<svg").count(), 4);
+        assert!(html.contains("<title>Part 1: 10.0µs</title>"));
+        assert!(html.contains("<title>Day 2 part 2: 500.0ns</title>"));
+        assert!(html.contains("Day 1 &middot; 1.0ms"));
+    }
+
+    #[test]
+    fn renders_report_without_history() {
+        let html = render(&get_mock_timings(), &History::default(), None);
+        assert!(html.contains("No history yet."));
+        assert_eq!(html.matches("<svg").count(), 2);
+    }
+}
@@ -1,79 +1,529 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
     process,
+    str::FromStr,
 };
 
-use crate::template::Day;
+use crate::template::puzzle::Puzzle;
+use crate::template::{
+    cli, latest_event, Clock, Config, Day, SystemClock, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+
+static DEFAULT_TEMPLATE: &str = "default";
+
+/// Templates shipped with the template, stored in `src/templates`.
+const BUILTIN_TEMPLATES: [(&str, &str); 4] = [
+    (
+        "default",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/default.txt"
+        )),
+    ),
+    (
+        "grid",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/grid.txt"
+        )),
+    ),
+    (
+        "parse-struct",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/parse-struct.txt"
+        )),
+    ),
+    (
+        "graph",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/graph.txt"
+        )),
+    ),
+];
+
+/// Names of all templates, user-defined templates are read from the configured `templates` directory.
+fn template_names(config: &Config) -> Vec<String> {
+    let mut names: Vec<String> = BUILTIN_TEMPLATES
+        .iter()
+        .map(|(name, _)| (*name).to_string())
+        .collect();
+
+    if let Ok(entries) = fs::read_dir(&config.paths.templates) {
+        for entry in entries.filter_map(Result::ok) {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "txt") {
+                if let Some(name) = path.file_stem().and_then(|x| x.to_str()) {
+                    names.push(name.into());
+                }
+            }
+        }
+    }
+
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Look up a template by name. User-defined templates take precedence over built-in ones with the same name.
+fn load_template(config: &Config, name: &str) -> Result<String, String> {
+    let path = Path::new(&config.paths.templates).join(format!("{name}.txt"));
+
+    if let Ok(template) = fs::read_to_string(&path) {
+        return Ok(template);
+    }
+
+    if let Some((_, template)) = BUILTIN_TEMPLATES.iter().find(|(x, _)| *x == name) {
+        return Ok((*template).to_string());
+    }
+
+    let names = template_names(config);
+    let suggestion = cli::suggest(name, names.iter().map(String::as_str))
+        .map_or_else(String::new, |x| format!(" Did you mean `{x}`?"));
+
+    Err(format!(
+        "unknown template `{name}`.{suggestion} Available templates: {}.",
+        names.join(", ")
+    ))
+}
+
+/// Values substituted into a template.
+struct Placeholders {
+    day: Day,
+    year: String,
+    title: String,
+    answers: [Option<String>; 2],
+}
+
+impl Placeholders {
+    fn new(config: &Config, day: Day, puzzle: Puzzle) -> Self {
+        Self {
+            day,
+            year: config.year.map_or_else(
+                || latest_event(SystemClock.now(), config.server.utc_offset).to_string(),
+                |year| year.to_string(),
+            ),
+            title: puzzle.title.unwrap_or_else(|| "TBD".into()),
+            answers: puzzle.answers,
+        }
+    }
+}
+
+/// The type a part function of a template returns, e.g. `u32` for `fn part_one(input: &str) -> Option<u32>`.
+fn return_type<'a>(template: &'a str, function: &str) -> Option<&'a str> {
+    let signature = &template[template.find(&format!("fn {function}("))?..];
+    let signature = &signature[..signature.find('{')?];
+    let (_, ty) = signature.split_once("-> Option<")?;
+    Some(ty[..ty.rfind('>')?].trim())
+}
+
+/// Render the expected example answer of a part as an expression, e.g. `Some(11)`.
+/// Answers that are not numbers of the return type `ty`, `u32` if unknown, are left to the user.
+fn answer_expression(answer: Option<&String>, ty: Option<&str>) -> String {
+    fn fits<T: FromStr>(answer: &str) -> bool {
+        answer.parse::<T>().is_ok()
+    }
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+    let Some(answer) = answer else {
+        return "None".into();
+    };
+
+    let is_valid = match ty.unwrap_or("u32") {
+        "u8" => fits::<u8>(answer),
+        "u16" => fits::<u16>(answer),
+        "u32" => fits::<u32>(answer),
+        "u64" => fits::<u64>(answer),
+        "u128" => fits::<u128>(answer),
+        "usize" => fits::<usize>(answer),
+        "i8" => fits::<i8>(answer),
+        "i16" => fits::<i16>(answer),
+        "i32" => fits::<i32>(answer),
+        "i64" => fits::<i64>(answer),
+        "i128" => fits::<i128>(answer),
+        "isize" => fits::<isize>(answer),
+        _ => false,
+    };
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
-    let mut file = OpenOptions::new();
-    if overwrite {
-        file.create(true);
+    if is_valid {
+        format!("Some({answer})")
     } else {
-        file.create_new(true);
+        "None".into()
     }
-    file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// Substitute the placeholders of a template:
+///  - `%DAY_NUMBER%`: the day without padding, e.g. `1`.
+///  - `%DAY_PADDED%`: the day padded to two digits, e.g. `01`.
+///  - `%YEAR%`: the configured year, or the latest event.
+///  - `%TITLE%`: the puzzle title, if the puzzle was downloaded.
+///  - `%EXAMPLE_ANSWER_1%`, `%EXAMPLE_ANSWER_2%`: the answers to the example, e.g. `Some(11)`, or `None`
+///    if an answer does not fit the return type of the part.
+fn render(template: &str, values: &Placeholders) -> String {
+    template
+        .replace("%DAY_NUMBER%", &values.day.into_inner().to_string())
+        .replace("%DAY_PADDED%", &values.day.to_string())
+        .replace("%YEAR%", &values.year)
+        .replace("%TITLE%", &values.title)
+        .replace(
+            "%EXAMPLE_ANSWER_1%",
+            &answer_expression(
+                values.answers[0].as_ref(),
+                return_type(template, "part_one"),
+            ),
+        )
+        .replace(
+            "%EXAMPLE_ANSWER_2%",
+            &answer_expression(
+                values.answers[1].as_ref(),
+                return_type(template, "part_two"),
+            ),
+        )
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Controls which template is used and which files may be replaced.
+#[derive(Clone, Debug)]
+pub struct ScaffoldOptions {
+    /// replace an existing module file, after backing it up. Inputs and examples are never replaced.
+    pub overwrite: bool,
+    /// print the plan without touching any files.
+    pub dry_run: bool,
+    pub template: String,
+}
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
+impl Default for ScaffoldOptions {
+    fn default() -> Self {
+        Self {
+            overwrite: false,
+            dry_run: false,
+            template: DEFAULT_TEMPLATE.into(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Action {
+    Create,
+    Skip,
+    Overwrite,
+}
+
+/// A file the scaffold command creates.
+struct Step {
+    kind: &'static str,
+    path: String,
+    contents: String,
+    action: Action,
+}
+
+fn plan(config: &Config, day: Day, options: &ScaffoldOptions, module: String) -> Vec<Step> {
+    let step = |kind, path: String, contents, can_overwrite: bool| {
+        let action = match (
+            Path::new(&path).exists(),
+            can_overwrite && options.overwrite,
+        ) {
+            (false, _) => Action::Create,
+            (true, true) => Action::Overwrite,
+            (true, false) => Action::Skip,
+        };
+
+        Step {
+            kind,
+            path,
+            contents,
+            action,
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+    vec![
+        step("module file", config.bin_path(day), module, true),
+        step("input file", config.input_path(day), String::new(), false),
+        step(
+            "example file",
+            config.example_path(day),
+            String::new(),
+            false,
+        ),
+    ]
+}
+
+/// The first free backup path for a file, e.g. `src/bin/01.rs.bak` or `src/bin/01.rs.bak.2`.
+fn backup_path(path: &str) -> String {
+    let mut backup = format!("{path}.bak");
+    let mut counter = 1;
+
+    while Path::new(&backup).exists() {
+        counter += 1;
+        backup = format!("{path}.bak.{counter}");
+    }
+
+    backup
+}
+
+/// Apply a step, returns the path of the backup if a file was replaced.
+fn apply(step: &Step) -> io::Result<Option<String>> {
+    match step.action {
+        Action::Skip => Ok(None),
+        Action::Create => {
+            if let Some(parent) = Path::new(&step.path).parent() {
+                fs::create_dir_all(parent)?;
+            }
+            // `create_new` guards against the file appearing since the plan was made.
+            OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&step.path)?
+                .write_all(step.contents.as_bytes())?;
+            Ok(None)
         }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+        Action::Overwrite => {
+            let backup = backup_path(&step.path);
+            fs::copy(&step.path, &backup)?;
+            fs::write(&step.path, &step.contents)?;
+            Ok(Some(backup))
         }
     }
+}
+
+/// Describe a step, as part of the plan or once it was applied.
+fn describe(step: &Step, applied: bool, backup: Option<&str>) -> String {
+    let Step {
+        kind, path, action, ..
+    } = step;
+    let empty = if step.contents.is_empty() {
+        "empty "
+    } else {
+        ""
+    };
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+    match (action, applied) {
+        (Action::Create, false) => format!("Create {empty}{kind} \"{path}\""),
+        (Action::Create, true) => format!("Created {empty}{kind} \"{path}\""),
+        (Action::Overwrite, false) => format!("Overwrite {kind} \"{path}\" after backing it up"),
+        (Action::Overwrite, true) => format!(
+            "Overwrote {kind} \"{path}\", backed up to \"{}\"",
+            backup.unwrap_or_default()
+        ),
+        (Action::Skip, false) => {
+            format!("Skip {kind} \"{path}\" {ANSI_ITALIC}(already exists){ANSI_RESET}")
         }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+        (Action::Skip, true) => {
+            format!("Skipped {kind} \"{path}\" {ANSI_ITALIC}(already exists){ANSI_RESET}")
         }
     }
+}
+
+/// Create the files of a day and print what was done, or only print the plan for a dry run.
+/// Returns the action taken for the module, input and example file.
+pub(crate) fn create_files(
+    config: &Config,
+    day: Day,
+    options: &ScaffoldOptions,
+) -> Result<Vec<Action>, String> {
+    let template = load_template(config, &options.template)?;
+    let puzzle = Puzzle::read(config, day).unwrap_or_default();
+    let module = render(&template, &Placeholders::new(config, day, puzzle));
+    let steps = plan(config, day, options, module);
+
+    if options.dry_run {
+        println!(
+            "{ANSI_BOLD}Plan{ANSI_RESET} {ANSI_ITALIC}(dry run, no files were changed){ANSI_RESET}"
+        );
+    }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    for step in &steps {
+        if options.dry_run {
+            println!("  {}", describe(step, false, None));
+            continue;
         }
+
+        let backup = apply(step)
+            .map_err(|e| format!("Failed to write {} \"{}\": {e}", step.kind, step.path))?;
+        println!("{}", describe(step, true, backup.as_deref()));
+    }
+
+    Ok(steps.iter().map(|step| step.action).collect())
+}
+
+/// Fill in the title and example answers of a module that was scaffolded before its puzzle was downloaded.
+/// Modules that were edited since are left untouched. Returns whether the module was updated.
+pub(crate) fn fill_puzzle_details(
+    config: &Config,
+    day: Day,
+    template: &str,
+) -> Result<bool, String> {
+    let Some(puzzle) = Puzzle::read(config, day) else {
+        return Ok(false);
+    };
+
+    let template = load_template(config, template)?;
+    let path = config.bin_path(day);
+    let current =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read \"{path}\": {e}"))?;
+
+    let untouched = render(
+        &template,
+        &Placeholders::new(config, day, Puzzle::default()),
+    );
+    let updated = render(&template, &Placeholders::new(config, day, puzzle));
+
+    if current != untouched || current == updated {
+        return Ok(false);
+    }
+
+    fs::write(&path, updated).map_err(|e| format!("Failed to write \"{path}\": {e}"))?;
+    Ok(true)
+}
+
+pub fn handle(config: &Config, day: Day, options: &ScaffoldOptions) {
+    let actions = match create_files(config, day, options) {
+        Ok(actions) => actions,
         Err(e) => {
-            eprintln!("Failed to create example file: {e}");
+            eprintln!("{e}");
             process::exit(1);
         }
+    };
+
+    if options.dry_run {
+        return;
+    }
+
+    if actions[0] == Action::Skip {
+        println!("{ANSI_ITALIC}Pass --overwrite to replace the module file, inputs and examples are never replaced.{ANSI_RESET}");
     }
 
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        apply, load_template, plan, render, Action, Placeholders, ScaffoldOptions,
+        BUILTIN_TEMPLATES,
+    };
+    use crate::day;
+    use crate::template::test_server::temp_dir;
+    use crate::template::Config;
+    use std::fs;
+
+    /// A configuration that scaffolds into a fresh temporary directory.
+    fn temp_config(name: &str) -> Config {
+        let dir = temp_dir(&format!("scaffold_{name}"));
+        let dir = dir.to_string_lossy().to_string();
+
+        let mut config = Config::default();
+        config.paths.bin = format!("{dir}/bin");
+        config.paths.inputs = format!("{dir}/inputs");
+        config.paths.examples = format!("{dir}/examples");
+        config
+    }
+
+    #[test]
+    fn never_replaces_inputs_and_examples() {
+        let config = temp_config("inputs");
+        let options = ScaffoldOptions {
+            overwrite: true,
+            ..ScaffoldOptions::default()
+        };
+
+        for step in plan(&config, day!(5), &options, "v1".into()) {
+            apply(&step).unwrap();
+        }
+        fs::write(config.example_path(day!(5)), "pasted example").unwrap();
+
+        let steps = plan(&config, day!(5), &options, "v2".into());
+        let actions: Vec<Action> = steps.iter().map(|step| step.action).collect();
+        assert_eq!(actions, [Action::Overwrite, Action::Skip, Action::Skip]);
+
+        let backups: Vec<Option<String>> = steps.iter().map(|step| apply(step).unwrap()).collect();
+        let backup = backups[0].clone().unwrap();
+        assert!(backup.ends_with("05.rs.bak"));
+        assert_eq!(fs::read_to_string(backup).unwrap(), "v1");
+        assert_eq!(fs::read_to_string(config.bin_path(day!(5))).unwrap(), "v2");
+        assert_eq!(
+            fs::read_to_string(config.example_path(day!(5))).unwrap(),
+            "pasted example"
+        );
+
+        // later backups do not replace earlier ones.
+        let steps = plan(&config, day!(5), &options, "v3".into());
+        assert!(apply(&steps[0]).unwrap().unwrap().ends_with("05.rs.bak.2"));
+    }
+
+    #[test]
+    fn skips_existing_files_without_overwrite() {
+        let config = temp_config("skip");
+        let options = ScaffoldOptions::default();
+
+        let steps = plan(&config, day!(6), &options, "v1".into());
+        assert!(steps.iter().all(|step| step.action == Action::Create));
+        for step in &steps {
+            apply(step).unwrap();
+        }
+
+        let steps = plan(&config, day!(6), &options, "v2".into());
+        assert!(steps.iter().all(|step| step.action == Action::Skip));
+        assert_eq!(fs::read_to_string(config.bin_path(day!(6))).unwrap(), "v1");
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let values = Placeholders {
+            day: day!(4),
+            year: "2023".into(),
+            title: "Ceres Search".into(),
+            answers: [Some("18".into()), Some("one".into())],
+        };
+
+        let rendered = render(
+            "%DAY_NUMBER% %DAY_PADDED% %YEAR% %TITLE% %EXAMPLE_ANSWER_1% %EXAMPLE_ANSWER_2%",
+            &values,
+        );
+        assert_eq!(rendered, "4 04 2023 Ceres Search Some(18) None");
+    }
+
+    #[test]
+    fn renders_answers_that_fit_the_return_type() {
+        let values = Placeholders {
+            day: day!(4),
+            year: "2023".into(),
+            title: "Ceres Search".into(),
+            answers: [Some("5000000000".into()), Some("5000000000".into())],
+        };
+
+        let template = "pub fn part_one(input: &str) -> Option<u32> {}
+pub fn part_two(input: &str) -> Option<u64> {}
+%EXAMPLE_ANSWER_1% %EXAMPLE_ANSWER_2%";
+        assert!(render(template, &values).ends_with("None Some(5000000000)"));
+        assert_eq!(render("%EXAMPLE_ANSWER_1%", &values), "None");
+    }
+
+    #[test]
+    fn renders_builtin_templates() {
+        let values = Placeholders {
+            day: day!(4),
+            year: "2023".into(),
+            title: "Ceres Search".into(),
+            answers: [None, None],
+        };
+
+        for (_, template) in BUILTIN_TEMPLATES {
+            let rendered = render(template, &values);
+            assert!(rendered.contains("advent_of_code::solution!(4"));
+            assert!(!rendered.contains("%DAY_"));
+            assert!(!rendered.contains("%EXAMPLE_ANSWER_"));
+        }
+    }
+
+    #[test]
+    fn suggests_unknown_templates() {
+        let config = Config::default();
+        assert!(load_template(&config, "grid").is_ok());
+        assert!(load_template(&config, "gird")
+            .unwrap_err()
+            .contains("Did you mean `grid`?"));
+    }
+}
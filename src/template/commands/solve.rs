@@ -1,30 +1,36 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::run_multi::RunOptions;
+use crate::template::{Config, Day, InputSource};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+pub fn handle(
+    config: &Config,
+    day: Day,
+    options: RunOptions,
+    submit_part: Option<u8>,
+    yes: bool,
+    input: &InputSource,
+) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
-    if dhat {
-        cmd_args.extend([
-            "--profile".to_string(),
-            "dhat".to_string(),
-            "--features".to_string(),
-            "dhat-heap".to_string(),
-        ]);
-    } else if release {
-        cmd_args.push("--release".to_string());
-    }
-
+    cmd_args.extend(options.cargo_args());
     cmd_args.push("--".to_string());
+    cmd_args.extend(options.binary_args());
 
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
+    if yes {
+        cmd_args.push("--yes".to_string());
+    }
+
+    cmd_args.extend(input.to_args());
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
+        .envs(config.env_vars())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
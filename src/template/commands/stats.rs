@@ -0,0 +1,93 @@
+use std::{fs, process};
+
+use crate::template::stats::{self, PartStats, Stats};
+use crate::template::timings::Timings;
+use crate::template::{readme_benchmarks, Clock, Config, SystemClock, ANSI_BOLD, ANSI_RESET};
+
+fn render(stats: &Stats) -> Vec<String> {
+    let time = |part: Option<&PartStats>| part.map_or_else(|| "-".into(), |p| p.time.clone());
+    let rank = |part: Option<&PartStats>| {
+        part.and_then(|p| p.rank)
+            .map_or_else(|| "-".into(), |rank| rank.to_string())
+    };
+
+    let mut lines = vec![format!(
+        "{ANSI_BOLD}{:<4}  {:<10}  {:>7}  {:<10}  {:>7}{ANSI_RESET}",
+        "Day", "Part 1", "Rank", "Part 2", "Rank"
+    )];
+
+    for day in &stats.data {
+        lines.push(format!(
+            "{:<4}  {:<10}  {:>7}  {:<10}  {:>7}",
+            day.day.to_string(),
+            time(day.part_1.as_ref()),
+            rank(day.part_1.as_ref()),
+            time(day.part_2.as_ref()),
+            rank(day.part_2.as_ref())
+        ));
+    }
+
+    lines
+}
+
+pub fn handle(config: &Config, file: Option<String>, readme: bool) {
+    let year = config.event_year(SystemClock.now());
+    let page = match &file {
+        Some(path) => fs::read_to_string(path).map_err(|e| format!("could not read {path}: {e}")),
+        None => stats::download(config, year),
+    };
+
+    let stats = match page.and_then(|page| Stats::parse_page(&page)) {
+        Ok(stats) => stats,
+        Err(e) => {
+            eprintln!("Failed to import personal stats: {e}");
+            process::exit(1);
+        }
+    };
+
+    for line in render(&stats) {
+        println!("{line}");
+    }
+
+    println!();
+    let path = config.stats_path(year);
+    if let Err(e) = stats.store_file(&path) {
+        eprintln!("Failed to store personal stats: {e}");
+        process::exit(1);
+    }
+    println!("Stored personal stats in {path}.");
+
+    if readme {
+        let timings = match Timings::read_from_path(&config.paths.timings) {
+            Ok(timings) => timings,
+            Err(e) => {
+                eprintln!("Failed to read stored timings: {e}");
+                process::exit(1);
+            }
+        };
+        match readme_benchmarks::update(config, timings) {
+            Ok(()) => println!("Updated the benchmark tables in the readme."),
+            Err(e) => {
+                eprintln!("Failed to update the readme: {e:?}");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::render;
+    use crate::template::stats::Stats;
+    use crate::template::test_server::STATS;
+
+    #[test]
+    fn renders_stats() {
+        let lines = render(&Stats::parse_page(STATS).unwrap());
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[1], "01    00:01:01         98  00:05:00       1234");
+        assert_eq!(lines[3], "03    >24h          23456  -                 -");
+    }
+}
@@ -0,0 +1,274 @@
+use std::{fs, process};
+
+use crate::template::progress::{DayProgress, Progress};
+use crate::template::readme_benchmarks::{locate_table, Error};
+use crate::template::submission;
+use crate::template::{
+    first_weekday_of_advent, AllDays, Config, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+
+const WEEKDAYS: [&str; 7] = ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"];
+
+pub fn handle(config: &Config, update_readme: bool) {
+    let progress = Progress::from_log(&submission::read_log(&config.paths.submissions))
+        .event(config.year, config.all_days());
+    let year = config.year;
+    let days = config.max_day();
+
+    print!("{}", render_terminal(&progress, year, days));
+
+    if update_readme {
+        println!();
+        match update(config, &progress, year, days) {
+            Ok(()) => println!("Updated progress in the readme."),
+            Err(e) => {
+                eprintln!("Failed to update progress in the readme: {e:?}");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/// The first `days` days of advent laid out in weeks, starting on monday.
+fn calendar(year: Option<i64>, days: u8) -> Vec<[Option<Day>; 7]> {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let offset = year.map_or(0, first_weekday_of_advent) as usize;
+
+    let mut weeks = vec![];
+    let mut week = [None; 7];
+
+    for (i, day) in AllDays::until(days).enumerate() {
+        let slot = (offset + i) % 7;
+        week[slot] = Some(day);
+
+        if slot == 6 {
+            weeks.push(week);
+            week = [None; 7];
+        }
+    }
+
+    if week.iter().any(Option::is_some) {
+        weeks.push(week);
+    }
+
+    weeks
+}
+
+fn stars(day: Option<&DayProgress>) -> usize {
+    day.map_or(0, DayProgress::stars)
+}
+
+fn render_terminal(progress: &Progress, year: Option<u16>, days: u8) -> String {
+    let total_days = usize::from(days);
+    let mut lines = vec![];
+
+    let title = year.map_or_else(
+        || "Advent of Code".into(),
+        |year| format!("Advent of Code {year}"),
+    );
+    lines.push(format!("{ANSI_BOLD}{title}{ANSI_RESET}"));
+    lines.push(String::new());
+    lines.push(
+        WEEKDAYS
+            .iter()
+            .map(|weekday| format!("{weekday:<5}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .into(),
+    );
+
+    for week in calendar(year.map(i64::from), days) {
+        let cells: Vec<String> = week
+            .iter()
+            .map(|day| match day {
+                None => " ".repeat(5),
+                Some(day) => {
+                    let stars = stars(progress.get(year, *day));
+                    let cell = format!("{day} {}{}", "★".repeat(stars), "☆".repeat(2 - stars));
+                    if stars == 2 {
+                        format!("{ANSI_BOLD}{cell}{ANSI_RESET}")
+                    } else {
+                        cell
+                    }
+                }
+            })
+            .collect();
+
+        lines.push(cells.join("  ").trim_end().into());
+    }
+
+    lines.push(String::new());
+    lines.push(format!("Stars: {}/{}", progress.stars(), total_days * 2));
+
+    for day in &progress.days {
+        for (index, part) in day.parts.iter().enumerate() {
+            if part.attempts == 0 && !part.solved {
+                continue;
+            }
+
+            let attempts = match part.attempts {
+                0 => String::new(),
+                1 => ", 1 attempt".into(),
+                n => format!(", {n} attempts"),
+            };
+
+            let status = if part.solved {
+                format!(
+                    "✔ {}{attempts}{}",
+                    part.date.as_deref().unwrap_or("solved"),
+                    part.answer
+                        .as_ref()
+                        .map_or_else(String::new, |answer| format!(
+                            " {ANSI_ITALIC}({answer}){ANSI_RESET}"
+                        ))
+                )
+            } else {
+                format!("✖ unsolved{attempts}")
+            };
+
+            lines.push(format!("  Day {} part {}: {status}", day.day, index + 1));
+        }
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn render_markdown(marker: &str, progress: &Progress, year: Option<u16>, days: u8) -> String {
+    let row = |cells: Vec<String>| format!("| {} |", cells.join(" | "));
+
+    let mut lines = vec![marker.into(), "## Progress".into(), String::new()];
+    lines.push(row(WEEKDAYS.iter().map(|x| (*x).into()).collect()));
+    lines.push(row(WEEKDAYS.iter().map(|_| ":---:".into()).collect()));
+
+    for week in calendar(year.map(i64::from), days) {
+        lines.push(row(week
+            .iter()
+            .map(|day| {
+                day.map_or_else(String::new, |day| {
+                    let stars = stars(progress.get(year, day));
+                    format!("{} {}", day.into_inner(), "⭐".repeat(stars))
+                        .trim_end()
+                        .into()
+                })
+            })
+            .collect()));
+    }
+
+    lines.push(String::new());
+    lines.push(format!(
+        "**Stars: {}/{}**",
+        progress.stars(),
+        usize::from(days) * 2
+    ));
+    lines.push(marker.into());
+
+    lines.join("\n")
+}
+
+fn update_content(
+    s: &mut String,
+    marker: &str,
+    progress: &Progress,
+    year: Option<u16>,
+    days: u8,
+) -> Result<(), Error> {
+    let positions = locate_table(s, marker)?;
+    s.replace_range(
+        positions.range(),
+        &render_markdown(marker, progress, year, days),
+    );
+    Ok(())
+}
+
+fn update(config: &Config, progress: &Progress, year: Option<u16>, days: u8) -> Result<(), Error> {
+    let path = &config.paths.readme;
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, &config.markers.progress, progress, year, days)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{calendar, render_terminal, update_content};
+    use crate::day;
+    use crate::template::progress::{Progress, SubmissionOutcome};
+    use crate::template::Config;
+
+    fn get_mock_progress() -> Progress {
+        let mut progress = Progress::default();
+        progress.record(
+            Some(2024),
+            day!(1),
+            1,
+            "42",
+            SubmissionOutcome::Correct,
+            "2024-12-01",
+        );
+        progress.record(
+            Some(2024),
+            day!(1),
+            2,
+            "43",
+            SubmissionOutcome::Correct,
+            "2024-12-01",
+        );
+        progress.record(
+            Some(2024),
+            day!(2),
+            1,
+            "7",
+            SubmissionOutcome::Incorrect,
+            "2024-12-02",
+        );
+        progress.record(
+            Some(2024),
+            day!(2),
+            1,
+            "8",
+            SubmissionOutcome::Correct,
+            "2024-12-02",
+        );
+        progress
+    }
+
+    #[test]
+    fn lays_out_calendar() {
+        // 2024-12-01 was a sunday.
+        let weeks = calendar(Some(2024), 25);
+        assert_eq!(weeks.len(), 5);
+        assert_eq!(weeks[0][6], Some(day!(1)));
+        assert_eq!(weeks[0][5], None);
+        assert_eq!(weeks[1][0], Some(day!(2)));
+        assert_eq!(weeks[4][2], Some(day!(25)));
+        assert_eq!(
+            calendar(Some(2025), 12).iter().flatten().flatten().count(),
+            12
+        );
+    }
+
+    #[test]
+    fn renders_terminal_status() {
+        let output = render_terminal(&get_mock_progress(), Some(2024), 25);
+        assert!(output.contains("Stars: 3/50"));
+        assert!(output.contains("02 ★☆"));
+        assert!(output.contains("Day 02 part 1: ✔ 2024-12-02, 2 attempts"));
+    }
+
+    #[test]
+    fn updates_readme() {
+        let marker = Config::default().markers.progress;
+        let mut s = format!("foo\n{marker}\n{marker}\nbar");
+        update_content(&mut s, &marker, &get_mock_progress(), Some(2024), 25).unwrap();
+        update_content(&mut s, &marker, &get_mock_progress(), Some(2024), 25).unwrap();
+
+        assert_eq!(s.matches(&marker).count(), 2);
+        assert!(s.contains("| Mo | Tu | We | Th | Fr | Sa | Su |"));
+        assert!(s.contains("|  |  |  |  |  |  | 1 ⭐⭐ |"));
+        assert!(s.contains("| 2 ⭐ | 3 | 4 |"));
+        assert!(s.contains("**Stars: 3/50**"));
+        assert!(s.ends_with("bar"));
+    }
+}
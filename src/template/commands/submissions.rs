@@ -0,0 +1,117 @@
+use crate::template::progress::SubmissionOutcome;
+use crate::template::submission::{self, Submission};
+use crate::template::{
+    format_date, Clock, Config, Day, SystemClock, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+
+/// A short verdict for a response of the server, e.g. `✖ too high`.
+fn verdict(response: &str) -> &'static str {
+    match SubmissionOutcome::parse(response) {
+        SubmissionOutcome::Correct => "✔ correct",
+        SubmissionOutcome::AlreadySolved => "✔ already solved",
+        SubmissionOutcome::Incorrect if response.contains("too high") => "✖ too high",
+        SubmissionOutcome::Incorrect if response.contains("too low") => "✖ too low",
+        SubmissionOutcome::Incorrect => "✖ incorrect",
+        SubmissionOutcome::Unchecked if response.contains("too recently") => "⏳ rate limited",
+        SubmissionOutcome::Unchecked => "? unknown",
+    }
+}
+
+fn format_time(timestamp: u64) -> String {
+    let seconds = timestamp % 86400;
+    format!(
+        "{} {:02}:{:02}:{:02}",
+        format_date(timestamp),
+        seconds / 3600,
+        (seconds % 3600) / 60,
+        seconds % 60
+    )
+}
+
+fn render(submissions: &[&Submission]) -> String {
+    let answer_width = submissions
+        .iter()
+        .map(|submission| submission.answer.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Answer".len());
+
+    let mut lines = vec![format!(
+        "{ANSI_BOLD}{:<19}  {:<4}  {:<4}  {:<answer_width$}  Verdict{ANSI_RESET}",
+        "Time (UTC)", "Day", "Part", "Answer"
+    )];
+
+    for submission in submissions {
+        lines.push(format!(
+            "{:<19}  {:<4}  {:<4}  {:<answer_width$}  {}",
+            format_time(submission.timestamp),
+            submission.day,
+            submission.part,
+            submission.answer,
+            verdict(&submission.response)
+        ));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+pub fn handle(config: &Config, day: Option<Day>) {
+    let log = submission::read_log(&config.paths.submissions);
+
+    // a log may cover several events, only list the configured one.
+    let submissions: Vec<&Submission> = log
+        .iter()
+        .filter(|submission| config.year.is_none() || submission.year == config.year)
+        .filter(|submission| day.is_none_or(|day| submission.day == day))
+        .collect();
+
+    if submissions.is_empty() {
+        println!("No submissions yet, submit an answer with `cargo solve <day> --release --submit <part>`.");
+        return;
+    }
+
+    print!("{}", render(&submissions));
+
+    let now = u64::try_from(SystemClock.now()).unwrap_or(0);
+    let mut days: Vec<Day> = submissions
+        .iter()
+        .map(|submission| submission.day)
+        .collect();
+    days.sort_unstable();
+    days.dedup();
+
+    for day in days {
+        if let Some(seconds) = submission::remaining_cooldown(&log, config.year, day, now) {
+            println!(
+                "{ANSI_ITALIC}Day {day}: wait {}m {:02}s before submitting again.{ANSI_RESET}",
+                seconds / 60,
+                seconds % 60
+            );
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_time, verdict};
+
+    #[test]
+    fn summarizes_responses() {
+        assert_eq!(verdict("That's the right answer!"), "✔ correct");
+        assert_eq!(
+            verdict("That's not the right answer; your answer is too low."),
+            "✖ too low"
+        );
+        assert_eq!(
+            verdict("You gave an answer too recently; you have to wait."),
+            "⏳ rate limited"
+        );
+    }
+
+    #[test]
+    fn formats_times() {
+        assert_eq!(format_time(1_733_029_262), "2024-12-01 05:01:02");
+    }
+}
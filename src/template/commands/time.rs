@@ -1,19 +1,30 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, process};
 
-use crate::template::run_multi::run_multi;
+use crate::template::history::History;
+use crate::template::run_multi::{run_multi, RunOptions};
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{readme_benchmarks, Config, Day};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+pub fn handle(config: &Config, day: Option<Day>, run_all: bool, store: bool, options: RunOptions) {
+    // stored timings and the history are merged with the new timings, they are never replaced by empty ones.
+    let read = Timings::read_from_path(&config.paths.timings)
+        .and_then(|timings| Ok((timings, History::read_from_file(&config.paths.history)?)));
+    let (stored_timings, mut history) = match read {
+        Ok(read) => read,
+        Err(e) => {
+            eprintln!("Failed to read stored timings: {e}");
+            process::exit(1);
+        }
+    };
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
-                all_days().collect()
+                config.all_days().collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
+                config
+                    .all_days()
                     .filter(|day| !stored_timings.is_day_complete(*day))
                     .collect()
             }
@@ -21,19 +32,35 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let options = RunOptions {
+        release: true,
+        ..options
+    };
+    let timings = run_multi(config, &days_to_run, options, true).unwrap();
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        // durations measured while profiling or counting allocations are skewed,
+        // only store the heap summaries and allocation counts.
+        let skewed = options.dhat || options.count_allocs;
+        let merged_timings = if skewed {
+            stored_timings.merge_allocations(&timings)
+        } else {
+            stored_timings.merge(&timings)
+        };
+        merged_timings.store_file(&config.paths.timings).unwrap();
+
+        if !skewed {
+            history.push(timings);
+            history.store_file(&config.paths.history).unwrap();
+        }
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(config, merged_timings) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+            Err(e) => {
+                eprintln!("Failed to store updated benchmarks: {e:?}");
             }
         }
     }
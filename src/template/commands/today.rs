@@ -0,0 +1,142 @@
+/// Scaffolds, downloads and reads the puzzle of the current day.
+/// With `--wait`, waits for the next puzzle to unlock first and shows a countdown until then.
+use std::{
+    io::{self, Write},
+    process,
+    time::Duration,
+};
+
+use crate::template::commands::{read, scaffold};
+use crate::template::{
+    aoc_cli, format_duration, next_unlock, puzzle_at, Clock, Config, Day, SystemClock, ANSI_BOLD,
+    ANSI_RESET,
+};
+
+/// A puzzle can take a few seconds to be published after it unlocks, so downloads are retried.
+const DOWNLOAD_ATTEMPTS: u32 = 5;
+const DOWNLOAD_RETRY_DELAY: Duration = Duration::from_secs(3);
+
+/// Sleeps until the unix timestamp `instant`, calling `tick` with the remaining seconds about once per second.
+fn wait_until(clock: &impl Clock, instant: i64, mut tick: impl FnMut(i64)) {
+    loop {
+        let remaining = instant - clock.now();
+        if remaining <= 0 {
+            return;
+        }
+        tick(remaining);
+        clock.sleep(Duration::from_secs(1));
+    }
+}
+
+fn download(config: &Config, day: Day, clock: &impl Clock) -> Result<(), String> {
+    for attempt in 1..=DOWNLOAD_ATTEMPTS {
+        match aoc_cli::download(config, day) {
+            Ok(_) => return Ok(()),
+            Err(aoc_cli::AocCommandError::BadExitStatus(_)) if attempt < DOWNLOAD_ATTEMPTS => {
+                println!(
+                    "Puzzle is not available yet, retrying in {}s...",
+                    DOWNLOAD_RETRY_DELAY.as_secs()
+                );
+                clock.sleep(DOWNLOAD_RETRY_DELAY);
+            }
+            Err(e) => return Err(format!("failed to call aoc-cli: {e}")),
+        }
+    }
+    unreachable!("the last attempt returns")
+}
+
+pub fn handle(config: &Config, wait: bool) {
+    run(config, wait, &SystemClock);
+}
+
+fn run(config: &Config, wait: bool, clock: &impl Clock) {
+    let utc_offset = config.server.utc_offset;
+
+    if aoc_cli::check().is_err() {
+        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+        process::exit(1);
+    }
+
+    let (year, day) = if wait {
+        let (year, day, unlock) = next_unlock(clock.now(), utc_offset, config.days);
+        println!("Waiting for day {day} of {year}, press Ctrl+C to cancel.");
+
+        wait_until(clock, unlock, |remaining| {
+            print!(
+                "\r⏳ {ANSI_BOLD}Day {day}{ANSI_RESET} unlocks in {}  ",
+                format_duration(remaining)
+            );
+            let _ = io::stdout().flush();
+        });
+
+        println!(
+            "\r🎄 {ANSI_BOLD}Day {day}{ANSI_RESET} is unlocked!{:16}",
+            ""
+        );
+        (year, day)
+    } else {
+        match puzzle_at(clock.now(), utc_offset, config.days) {
+            Some(puzzle) => puzzle,
+            None => {
+                let (year, day, _) = next_unlock(clock.now(), utc_offset, config.days);
+                eprintln!(
+                    "`today` command can only be run while an event is running. \
+                    The next puzzle is day {day} of {year}: run `cargo today --wait` to wait for it, \
+                    or use `scaffold` with a specific day."
+                );
+                process::exit(1);
+            }
+        }
+    };
+
+    // the puzzle of today always belongs to the running event, whatever year is configured.
+    let mut config = config.clone();
+    config.year = u16::try_from(year).ok();
+
+    scaffold::handle(&config, day, &scaffold::ScaffoldOptions::default());
+
+    if let Err(e) = download(&config, day, clock) {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+
+    read::handle(&config, day);
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::wait_until;
+    use crate::template::Clock;
+    use std::{cell::Cell, time::Duration};
+
+    /// A clock that only advances when slept on.
+    struct FakeClock(Cell<i64>);
+
+    impl Clock for FakeClock {
+        fn now(&self) -> i64 {
+            self.0.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.0
+                .set(self.0.get() + i64::try_from(duration.as_secs()).unwrap());
+        }
+    }
+
+    #[test]
+    fn waits_until_unlock() {
+        let clock = FakeClock(Cell::new(1_000));
+        let mut ticks = vec![];
+
+        wait_until(&clock, 1_003, |remaining| ticks.push(remaining));
+
+        assert_eq!(ticks, vec![3, 2, 1]);
+        assert_eq!(clock.now(), 1_003);
+
+        // an unlocked puzzle does not wait.
+        wait_until(&clock, 900, |_| panic!("should not wait"));
+        assert_eq!(clock.now(), 1_003);
+    }
+}
@@ -0,0 +1,147 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::template::run_multi::{child_commands, RunOptions};
+use crate::template::{Config, Day, InputSource, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+type Answers = [Option<String>; 2];
+
+enum Verdict {
+    Passed,
+    Failed(Vec<usize>),
+    Unverified,
+    /// the solution did not print any answers, e.g. because it does not build.
+    Unsolved,
+}
+
+/// Read the known answers for an input. These are stored next to the input as `<name>.answers`,
+/// with one `<part>: <answer>` line per known part, e.g. `1: 42`.
+fn read_answers(input_path: &Path) -> Option<Answers> {
+    let contents = fs::read_to_string(input_path.with_extension("answers")).ok()?;
+    let mut answers: Answers = [None, None];
+
+    for line in contents.lines() {
+        let Some((part, answer)) = line.split_once(':') else {
+            continue;
+        };
+
+        match part.trim() {
+            "1" => answers[0] = Some(answer.trim().to_string()),
+            "2" => answers[1] = Some(answer.trim().to_string()),
+            _ => eprintln!("Ignoring unexpected line in answers file: {line}"),
+        }
+    }
+
+    Some(answers)
+}
+
+fn compare(expected: Option<&Answers>, actual: &Answers) -> Verdict {
+    let Some(expected) = expected else {
+        return Verdict::Unverified;
+    };
+
+    let failed: Vec<usize> = expected
+        .iter()
+        .zip(actual)
+        .enumerate()
+        .filter_map(|(index, (expected, actual))| match expected {
+            Some(expected) if Some(expected) != actual.as_ref() => Some(index + 1),
+            _ => None,
+        })
+        .collect();
+
+    if failed.is_empty() {
+        Verdict::Passed
+    } else {
+        Verdict::Failed(failed)
+    }
+}
+
+fn collect_inputs(dir: &Path) -> Vec<PathBuf> {
+    let mut inputs: Vec<PathBuf> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+            .collect(),
+        Err(e) => {
+            eprintln!("Failed to read input directory \"{}\": {e}", dir.display());
+            process::exit(1);
+        }
+    };
+
+    inputs.sort();
+    inputs
+}
+
+pub fn handle(config: &Config, day: Day, dir: Option<String>, is_release: bool) {
+    let dir = dir.map_or_else(
+        || PathBuf::from(format!("{}/{day}", config.paths.inputs)),
+        PathBuf::from,
+    );
+    let inputs = collect_inputs(&dir);
+    let options = RunOptions {
+        release: is_release,
+        ..RunOptions::default()
+    };
+
+    if inputs.is_empty() {
+        eprintln!("No inputs found in \"{}\".", dir.display());
+        process::exit(1);
+    }
+
+    let mut results: Vec<(String, Verdict)> = Vec::with_capacity(inputs.len());
+
+    for (i, path) in inputs.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        let name = path.display().to_string();
+        println!("{ANSI_BOLD}{name}{ANSI_RESET}");
+        println!("------");
+
+        let input = InputSource::File(name.clone());
+        let output = child_commands::run_solution(config, day, false, options, &input).unwrap();
+
+        let verdict = if output.is_empty() {
+            println!("Not solved.");
+            Verdict::Unsolved
+        } else {
+            let actual = child_commands::parse_answers(&output);
+            compare(read_answers(path).as_ref(), &actual)
+        };
+        results.push((name, verdict));
+    }
+
+    println!();
+    println!("{ANSI_BOLD}Summary{ANSI_RESET}");
+    println!("------");
+
+    let mut has_failures = false;
+
+    for (name, verdict) in &results {
+        match verdict {
+            Verdict::Passed => println!("✔ {name}"),
+            Verdict::Failed(parts) => {
+                has_failures = true;
+                let parts: Vec<String> = parts.iter().map(|p| format!("part {p}")).collect();
+                println!("✖ {name} ({} wrong)", parts.join(", "));
+            }
+            Verdict::Unverified => {
+                println!("? {name} {ANSI_ITALIC}(no answers file){ANSI_RESET}");
+            }
+            Verdict::Unsolved => {
+                has_failures = true;
+                println!("✖ {name} (not solved)");
+            }
+        }
+    }
+
+    if has_failures {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,114 @@
+use std::{
+    fs,
+    path::PathBuf,
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::run_multi::{child_commands, RunOptions};
+use crate::template::{Config, Day, InputSource, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+type Answers = [Option<String>; 2];
+
+/// Collect the files that affect a day's solution: its module, input and (partial) examples.
+/// Collected on every poll, so that examples for individual parts are picked up once they are created.
+fn watched_paths(config: &Config, day: Day) -> Vec<PathBuf> {
+    let mut paths = vec![
+        PathBuf::from(config.bin_path(day)),
+        PathBuf::from(config.input_path(day)),
+        PathBuf::from(config.example_path(day)),
+    ];
+
+    // examples for individual parts follow the `read_file_part` naming, e.g. `01-2.txt`.
+    if let Ok(entries) = fs::read_dir(&config.paths.examples) {
+        let mut parts: Vec<PathBuf> = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with(&format!("{day}-")))
+            })
+            .collect();
+        parts.sort();
+        paths.extend(parts);
+    }
+
+    paths
+}
+
+fn snapshot(paths: &[PathBuf]) -> Vec<(PathBuf, Option<SystemTime>)> {
+    paths
+        .iter()
+        .map(|path| {
+            let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+            (path.clone(), modified)
+        })
+        .collect()
+}
+
+fn run_tests(config: &Config, day: Day) -> bool {
+    Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &day.to_string()])
+        .envs(config.env_vars())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+fn print_diff(previous: Option<&Answers>, current: &Answers) {
+    println!("{ANSI_BOLD}Answers{ANSI_RESET}");
+
+    for (index, answer) in current.iter().enumerate() {
+        let part = index + 1;
+        let before = previous.and_then(|p| p[index].as_ref());
+        let now = answer.as_deref().unwrap_or("✖");
+
+        match (before, answer) {
+            (None, _) => println!("  Part {part}: {now}"),
+            (Some(before), Some(answer)) if before == answer => {
+                println!("  Part {part}: {now} {ANSI_ITALIC}(unchanged){ANSI_RESET}");
+            }
+            (Some(before), _) => println!("  Part {part}: {before} → {ANSI_BOLD}{now}{ANSI_RESET}"),
+        }
+    }
+}
+
+pub fn handle(config: &Config, day: Day, options: RunOptions) {
+    let mut last_snapshot = None;
+    let mut previous: Option<Answers> = None;
+
+    loop {
+        let current_snapshot = snapshot(&watched_paths(config, day));
+
+        if last_snapshot.as_ref() != Some(&current_snapshot) {
+            last_snapshot = Some(current_snapshot);
+
+            println!("{ANSI_BOLD}Day {day}{ANSI_RESET} {ANSI_ITALIC}(watching for changes, press Ctrl-C to exit){ANSI_RESET}");
+            println!("------");
+
+            if !run_tests(config, day) {
+                println!("{ANSI_ITALIC}Tests failed, running solution anyway.{ANSI_RESET}");
+            }
+
+            match child_commands::run_solution(config, day, false, options, &InputSource::Puzzle) {
+                Ok(output) if !output.is_empty() => {
+                    let answers = child_commands::parse_answers(&output);
+                    println!();
+                    print_diff(previous.as_ref(), &answers);
+                    previous = Some(answers);
+                }
+                Ok(_) => println!("Not solved."),
+                Err(e) => eprintln!("Failed to run solution: {e:?}"),
+            }
+
+            println!();
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
@@ -0,0 +1,653 @@
+/// Project configuration, read from `aoc.toml` in the project root.
+///
+/// Values are resolved in this order, later sources take precedence:
+///  1. built-in defaults.
+///  2. the configuration file, `aoc.toml` or the file named by `AOC_CONFIG` / `--config`.
+///  3. environment variables, named after the key, e.g. `AOC_YEAR` or `AOC_PATHS_INPUTS` for `paths.inputs`.
+///  4. command-line overrides, e.g. `--year 2023` or `--set paths.inputs=inputs`.
+///
+/// ```toml
+/// year = 2024
+/// days = 25
+///
+/// [paths]
+/// inputs = "data/inputs"
+/// bin = "src/bin"
+///
+/// [markers]
+/// benchmarks = "<!--- benchmarking table --->"
+///
+/// [server]
+/// utc_offset = -5
+/// url = "https://adventofcode.com"
+/// ```
+use std::{env, fs, process, sync::OnceLock};
+
+use crate::template::readme_benchmarks::TableConfig;
+use crate::template::{aoc_cli, event_days, latest_event, AllDays, Day};
+
+static CONFIG_FILE_PATH: &str = "aoc.toml";
+
+/// Keys that can be set, in the order they are listed in error messages.
+const KEYS: [&str; 20] = [
+    "year",
+    "days",
+    "paths.inputs",
+    "paths.examples",
+    "paths.puzzles",
+    "paths.bin",
+    "paths.timings",
+    "paths.stats",
+    "paths.history",
+    "paths.submissions",
+    "paths.readme",
+    "paths.templates",
+    "paths.cache",
+    "paths.visualizations",
+    "paths.profiles",
+    "paths.report",
+    "markers.benchmarks",
+    "markers.progress",
+    "server.utc_offset",
+    "server.url",
+];
+
+/// Locations of the files read and written by the template.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Paths {
+    pub inputs: String,
+    pub examples: String,
+    pub puzzles: String,
+    /// directory of the solution binaries.
+    pub bin: String,
+    pub timings: String,
+    /// personal ranks and solve times, imported with `cargo stats`. `{year}` is replaced with the year of the event.
+    pub stats: String,
+    pub history: String,
+    /// log of submitted answers and the responses of the server.
+    pub submissions: String,
+    pub readme: String,
+    /// directory of user-defined scaffold templates.
+    pub templates: String,
+    /// directory of downloaded data that is reused for a while, e.g. leaderboards.
+    pub cache: String,
+    /// directory of the frames exported with `--export`.
+    pub visualizations: String,
+    /// directory of the flamegraphs written with `--profile-cpu`.
+    pub profiles: String,
+    /// default output file of `cargo report`.
+    pub report: String,
+}
+
+/// Markers that delimit the generated sections of the readme.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Markers {
+    pub benchmarks: String,
+    pub progress: String,
+}
+
+/// The Advent of Code server.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Server {
+    /// offset of the server timezone to UTC in hours, puzzles unlock at midnight in this timezone.
+    pub utc_offset: i32,
+    /// base url of the server private leaderboards and personal stats are fetched from.
+    pub url: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Config {
+    /// year of the event, lets aoc-cli pick the latest event when not set.
+    pub year: Option<u16>,
+    /// number of puzzles of the event, see [`Config::max_day`].
+    pub days: Option<u8>,
+    pub paths: Paths,
+    pub markers: Markers,
+    pub server: Server,
+    /// benchmark tables in the readme, the first one is the unnamed table set in `[benchmarks]`.
+    pub tables: Vec<TableConfig>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: None,
+            days: None,
+            paths: Paths {
+                inputs: "data/inputs".into(),
+                examples: "data/examples".into(),
+                puzzles: "data/puzzles".into(),
+                bin: "src/bin".into(),
+                timings: "data/timings.json".into(),
+                stats: "data/{year}/stats.json".into(),
+                history: "data/timings_history.json".into(),
+                submissions: "data/submissions.jsonl".into(),
+                readme: "README.md".into(),
+                templates: "templates".into(),
+                cache: "data/cache".into(),
+                visualizations: "data/visualizations".into(),
+                profiles: "data/profiles".into(),
+                report: "data/report.html".into(),
+            },
+            markers: Markers {
+                benchmarks: "<!--- benchmarking table --->".into(),
+                progress: "<!--- progress calendar --->".into(),
+            },
+            server: Server {
+                utc_offset: -5,
+                url: aoc_cli::OFFICIAL_URL.into(),
+            },
+            tables: vec![TableConfig::default()],
+        }
+    }
+}
+
+impl Config {
+    /// The configuration of the running process, loaded once from `aoc.toml` and the environment.
+    /// Solution binaries receive the configuration of the CLI through the environment, see [`Config::env_vars`].
+    /// Exits if the configuration is invalid.
+    pub fn current() -> &'static Self {
+        static CURRENT: OnceLock<Config> = OnceLock::new();
+        CURRENT.get_or_init(Self::load)
+    }
+
+    fn load() -> Self {
+        match Self::load_from(None) {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("Invalid configuration: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    /// Load the configuration from the file at `path` and the environment.
+    /// Without a `path`, reads `AOC_CONFIG` or `aoc.toml`, which may be absent.
+    pub fn load_from(path: Option<&str>) -> Result<Self, String> {
+        let explicit_path = path
+            .map(String::from)
+            .or_else(|| env::var("AOC_CONFIG").ok().filter(|x| !x.is_empty()));
+
+        let mut config = Config::default();
+
+        match &explicit_path {
+            Some(path) => {
+                let contents =
+                    fs::read_to_string(path).map_err(|e| format!("could not read {path}: {e}"))?;
+                config
+                    .apply_toml(&contents)
+                    .map_err(|e| format!("{path}: {e}"))?;
+            }
+            None => {
+                if let Ok(contents) = fs::read_to_string(CONFIG_FILE_PATH) {
+                    config
+                        .apply_toml(&contents)
+                        .map_err(|e| format!("{CONFIG_FILE_PATH}: {e}"))?;
+                }
+            }
+        }
+
+        config.apply_env(env::vars())?;
+        Ok(config)
+    }
+
+    /// Set a single value, `key` uses the dotted form of the configuration file, e.g. `paths.inputs`.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        let int = |value: &str| {
+            value
+                .replace('_', "")
+                .parse::<i32>()
+                .map_err(|_| format!("expected `{key}` to be an integer, got `{value}`."))
+        };
+
+        match key {
+            "year" => {
+                self.year = if value.is_empty() {
+                    None
+                } else {
+                    Some(
+                        value
+                            .parse()
+                            .map_err(|_| format!("expected `year` to be a year, got `{value}`."))?,
+                    )
+                };
+            }
+            "days" => {
+                self.days = if value.is_empty() {
+                    None
+                } else {
+                    let days = value
+                        .parse()
+                        .ok()
+                        .filter(|days| (1..=Day::LIMIT).contains(days));
+                    Some(days.ok_or_else(|| {
+                        format!(
+                            "expected `days` to be a number between 1 and {}, got `{value}`.",
+                            Day::LIMIT
+                        )
+                    })?)
+                };
+            }
+            "paths.inputs" => self.paths.inputs = value.into(),
+            "paths.examples" => self.paths.examples = value.into(),
+            "paths.puzzles" => self.paths.puzzles = value.into(),
+            "paths.bin" => self.paths.bin = value.into(),
+            "paths.timings" => self.paths.timings = value.into(),
+            "paths.stats" => self.paths.stats = value.into(),
+            "paths.history" => self.paths.history = value.into(),
+            "paths.submissions" => self.paths.submissions = value.into(),
+            "paths.readme" => self.paths.readme = value.into(),
+            "paths.templates" => self.paths.templates = value.into(),
+            "paths.cache" => self.paths.cache = value.into(),
+            "paths.visualizations" => self.paths.visualizations = value.into(),
+            "paths.profiles" => self.paths.profiles = value.into(),
+            "paths.report" => self.paths.report = value.into(),
+            "markers.benchmarks" => self.markers.benchmarks = value.into(),
+            "markers.progress" => self.markers.progress = value.into(),
+            "server.utc_offset" => self.server.utc_offset = int(value)?,
+            "server.url" => self.server.url = value.into(),
+            _ => {
+                if let Some(key) = key.strip_prefix("benchmarks.") {
+                    return self.set_table(key, value);
+                }
+
+                return Err(format!(
+                    "unknown key `{key}`, expected one of: {}, or a key of a benchmark table, e.g. `benchmarks.title`.",
+                    KEYS.join(", ")
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Set a value of a benchmark table, `key` is either a key of the unnamed table, e.g. `title`,
+    /// or prefixed with the name of the table, e.g. `2023.title`.
+    fn set_table(&mut self, key: &str, value: &str) -> Result<(), String> {
+        let (name, table_key) = match key.split_once('.') {
+            Some((name, table_key)) => (Some(name), table_key),
+            None => (None, key),
+        };
+
+        let index = match self.tables.iter().position(|t| t.name.as_deref() == name) {
+            Some(index) => index,
+            None => {
+                self.tables.push(TableConfig {
+                    name: name.map(String::from),
+                    ..TableConfig::default()
+                });
+                self.tables.len() - 1
+            }
+        };
+
+        self.tables[index]
+            .set(table_key, value)
+            .map_err(|e| format!("`benchmarks.{key}`: {e}"))
+    }
+
+    /// Apply a command-line override of the form `key=value`.
+    pub fn apply_override(&mut self, s: &str) -> Result<(), String> {
+        let (key, value) = s
+            .split_once('=')
+            .ok_or_else(|| format!("expected an override of the form `key=value`, got `{s}`."))?;
+        self.set(key.trim(), value.trim())
+    }
+
+    fn apply_toml(&mut self, s: &str) -> Result<(), String> {
+        for (key, value) in parse_toml(s)? {
+            self.set(&key, &value)?;
+        }
+        Ok(())
+    }
+
+    fn apply_env(&mut self, vars: impl Iterator<Item = (String, String)>) -> Result<(), String> {
+        for (name, value) in vars {
+            if let Some(key) = KEYS.iter().find(|key| env_name(key) == name) {
+                self.set(key, &value)
+                    .map_err(|e| format!("environment variable {name}: {e}"))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn get(&self, key: &str) -> String {
+        match key {
+            "year" => self.year.map_or_else(String::new, |year| year.to_string()),
+            "days" => self.days.map_or_else(String::new, |days| days.to_string()),
+            "paths.inputs" => self.paths.inputs.clone(),
+            "paths.examples" => self.paths.examples.clone(),
+            "paths.puzzles" => self.paths.puzzles.clone(),
+            "paths.bin" => self.paths.bin.clone(),
+            "paths.timings" => self.paths.timings.clone(),
+            "paths.stats" => self.paths.stats.clone(),
+            "paths.history" => self.paths.history.clone(),
+            "paths.submissions" => self.paths.submissions.clone(),
+            "paths.readme" => self.paths.readme.clone(),
+            "paths.templates" => self.paths.templates.clone(),
+            "paths.cache" => self.paths.cache.clone(),
+            "paths.visualizations" => self.paths.visualizations.clone(),
+            "paths.profiles" => self.paths.profiles.clone(),
+            "paths.report" => self.paths.report.clone(),
+            "markers.benchmarks" => self.markers.benchmarks.clone(),
+            "markers.progress" => self.markers.progress.clone(),
+            "server.utc_offset" => self.server.utc_offset.to_string(),
+            "server.url" => self.server.url.clone(),
+            _ => unreachable!("unknown configuration key {key}"),
+        }
+    }
+
+    /// The configuration as environment variables, passed on to child processes so that
+    /// solution binaries see the same configuration, including command-line overrides.
+    pub fn env_vars(&self) -> Vec<(String, String)> {
+        KEYS.iter()
+            .map(|key| (env_name(key), self.get(key)))
+            .collect()
+    }
+
+    /// The last day of the event: `days` if set, else the length of the event of `year`, else 25.
+    pub fn max_day(&self) -> u8 {
+        self.days
+            .unwrap_or_else(|| self.year.map_or(25, |year| event_days(year.into())))
+    }
+
+    /// The days of the event, from the 1st to the [last day](Config::max_day).
+    pub fn all_days(&self) -> AllDays {
+        AllDays::until(self.max_day())
+    }
+
+    /// Check that `day` belongs to the event, i.e. is not after the [last day](Config::max_day).
+    pub fn check_day(&self, day: Day) -> Result<Day, String> {
+        if day > self.max_day() {
+            return Err(format!(
+                "day {day} is not part of the event, expecting a day number between 1 and {}.",
+                self.max_day()
+            ));
+        }
+        Ok(day)
+    }
+
+    /// The configured year, else the latest event at the unix timestamp `now`.
+    pub fn event_year(&self, now: i64) -> i64 {
+        self.year
+            .map_or_else(|| latest_event(now, self.server.utc_offset), i64::from)
+    }
+
+    /// The personal stats file of the event `year`.
+    pub fn stats_path(&self, year: i64) -> String {
+        self.paths.stats.replace("{year}", &year.to_string())
+    }
+
+    pub fn input_path(&self, day: Day) -> String {
+        format!("{}/{day}.txt", self.paths.inputs)
+    }
+
+    pub fn example_path(&self, day: Day) -> String {
+        format!("{}/{day}.txt", self.paths.examples)
+    }
+
+    pub fn puzzle_path(&self, day: Day) -> String {
+        format!("{}/{day}.md", self.paths.puzzles)
+    }
+
+    pub fn bin_path(&self, day: Day) -> String {
+        format!("{}/{day}.rs", self.paths.bin)
+    }
+}
+
+/// Name of the environment variable that overrides `key`, e.g. `AOC_PATHS_INPUTS` for `paths.inputs`.
+fn env_name(key: &str) -> String {
+    format!("AOC_{}", key.replace('.', "_").to_uppercase())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Parse the subset of TOML needed for the configuration: `[table]` headers, `key = value` pairs
+/// with string, integer and boolean values, and comments.
+/// Returns the values by their dotted key, e.g. `paths.inputs`, strings are unquoted.
+fn parse_toml(s: &str) -> Result<Vec<(String, String)>, String> {
+    let mut table: Option<String> = None;
+    let mut values = vec![];
+
+    for (index, line) in s.lines().enumerate() {
+        let error = |message: &str| format!("line {}: {message}", index + 1);
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix('[') {
+            let (name, rest) = header
+                .split_once(']')
+                .ok_or_else(|| error("expected `]` to close the table header."))?;
+            if !is_comment(rest) {
+                return Err(error("unexpected characters after the table header."));
+            }
+            let name = parse_key(name).ok_or_else(|| error("invalid table name."))?;
+            table = Some(name);
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| error("expected `key = value`."))?;
+        let key = parse_key(key).ok_or_else(|| error("invalid key."))?;
+        let value = parse_value(value.trim()).map_err(|e| error(&e))?;
+
+        let key = match &table {
+            Some(table) => format!("{table}.{key}"),
+            None => key,
+        };
+
+        if values.iter().any(|(k, _)| k == &key) {
+            return Err(error(&format!("duplicate key `{key}`.")));
+        }
+
+        values.push((key, value));
+    }
+
+    Ok(values)
+}
+
+fn is_comment(s: &str) -> bool {
+    let s = s.trim();
+    s.is_empty() || s.starts_with('#')
+}
+
+/// Parse a bare, possibly dotted, key.
+fn parse_key(s: &str) -> Option<String> {
+    let parts: Vec<&str> = s.split('.').map(str::trim).collect();
+    let valid = parts.iter().all(|part| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    });
+    valid.then(|| parts.join("."))
+}
+
+fn parse_value(s: &str) -> Result<String, String> {
+    let (value, rest) = if let Some(s) = s.strip_prefix('"') {
+        parse_basic_string(s)?
+    } else if let Some(s) = s.strip_prefix('\'') {
+        let end = s.find('\'').ok_or("unterminated string.")?;
+        (s[..end].to_string(), &s[end + 1..])
+    } else {
+        let end = s.find('#').unwrap_or(s.len());
+        let value = s[..end].trim();
+        let is_integer = value
+            .strip_prefix(['+', '-'])
+            .unwrap_or(value)
+            .replace('_', "")
+            .parse::<u64>()
+            .is_ok();
+
+        if value != "true" && value != "false" && !is_integer {
+            return Err(format!("unsupported value `{value}`."));
+        }
+
+        (value.trim_start_matches('+').to_string(), "")
+    };
+
+    if is_comment(rest) {
+        Ok(value)
+    } else {
+        Err("unexpected characters after the value.".into())
+    }
+}
+
+/// Parse the remainder of a double-quoted string, returns the string and the rest of the line.
+fn parse_basic_string(s: &str) -> Result<(String, &str), String> {
+    let mut value = String::new();
+    let mut chars = s.char_indices();
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '"' => return Ok((value, &s[index + 1..])),
+            '\\' => match chars.next().map(|(_, c)| c) {
+                Some('"') => value.push('"'),
+                Some('\\') => value.push('\\'),
+                Some('n') => value.push('\n'),
+                Some('t') => value.push('\t'),
+                Some(c) => return Err(format!("unsupported escape sequence `\\{c}`.")),
+                None => break,
+            },
+            c => value.push(c),
+        }
+    }
+
+    Err("unterminated string.".into())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_toml, Config};
+
+    #[test]
+    fn parses_toml_subset() {
+        let values = parse_toml(
+            r#"
+# project settings
+year = 2023 # trailing comment
+
+[paths]
+inputs = "inputs # not a comment"
+bin = 'solutions\bin'
+
+[server]
+utc_offset = -5
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            values,
+            vec![
+                ("year".into(), "2023".into()),
+                ("paths.inputs".into(), "inputs # not a comment".into()),
+                ("paths.bin".into(), "solutions\\bin".into()),
+                ("server.utc_offset".into(), "-5".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_toml() {
+        assert!(parse_toml("year").unwrap_err().starts_with("line 1"));
+        assert!(parse_toml("[paths\ninputs = \"a\"").is_err());
+        assert!(parse_toml("\n\ninputs = \"a")
+            .unwrap_err()
+            .starts_with("line 3"));
+        assert!(parse_toml("inputs = a").is_err());
+        assert!(parse_toml("year = 1\nyear = 2").is_err());
+    }
+
+    #[test]
+    fn applies_sources_in_order() {
+        let mut config = Config::default();
+        config
+            .apply_toml("year = 2023\n[paths]\ninputs = \"inputs\"\nexamples = \"examples\"")
+            .unwrap();
+        config
+            .apply_env(
+                [
+                    ("AOC_PATHS_INPUTS".to_string(), "env_inputs".to_string()),
+                    ("HOME".to_string(), "/root".to_string()),
+                ]
+                .into_iter(),
+            )
+            .unwrap();
+        config.apply_override("year=2022").unwrap();
+
+        assert_eq!(config.year, Some(2022));
+        assert_eq!(config.paths.inputs, "env_inputs");
+        assert_eq!(config.paths.examples, "examples");
+        assert_eq!(config.paths.bin, "src/bin");
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        let mut config = Config::default();
+        assert!(config.set("year", "next").is_err());
+        assert!(config.set("server.utc_offset", "east").is_err());
+        assert!(config.set("days", "0").is_err());
+        assert!(config.set("days", "100").is_err());
+        assert!(config.set("paths.unknown", "x").is_err());
+        assert!(config.apply_override("year").is_err());
+        assert!(config.set("benchmarks.sort", "random").is_err());
+        assert!(config.set("benchmarks.2023.colour", "red").is_err());
+    }
+
+    #[test]
+    fn configures_benchmark_tables() {
+        let mut config = Config::default();
+        config
+            .apply_toml(
+                "[benchmarks]\ntitle = \"Timings\"\n\n[benchmarks.2023]\nheading_level = 3\nyear = 2023",
+            )
+            .unwrap();
+
+        assert_eq!(config.tables.len(), 2);
+        assert_eq!(config.tables[0].name, None);
+        assert_eq!(config.tables[0].title, "Timings");
+        assert_eq!(config.tables[1].name.as_deref(), Some("2023"));
+        assert_eq!(config.tables[1].heading_level, 3);
+        assert_eq!(config.tables[1].year.as_deref(), Some("2023"));
+    }
+
+    #[test]
+    fn resolves_max_day() {
+        let mut config = Config::default();
+        assert_eq!(config.max_day(), 25);
+
+        config.set("year", "2025").unwrap();
+        assert_eq!(config.max_day(), 12);
+
+        config.set("days", "20").unwrap();
+        assert_eq!(config.max_day(), 20);
+    }
+
+    #[test]
+    fn keys_stats_by_year() {
+        let mut config = Config::default();
+        // 2024-06-01, before the event of 2024.
+        assert_eq!(config.event_year(1_717_200_000), 2023);
+        assert_eq!(config.stats_path(2023), "data/2023/stats.json");
+
+        config.set("year", "2025").unwrap();
+        assert_eq!(config.event_year(1_717_200_000), 2025);
+    }
+
+    #[test]
+    fn round_trips_env_vars() {
+        let mut config = Config::default();
+        config.set("year", "2023").unwrap();
+        config.set("server.utc_offset", "1").unwrap();
+
+        let mut parsed = Config::default();
+        parsed.apply_env(config.env_vars().into_iter()).unwrap();
+        assert_eq!(parsed, config);
+    }
+}
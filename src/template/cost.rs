@@ -0,0 +1,180 @@
+/// Hardware-independent cost measurement for solution parts, enabled by the `cost-counters` feature.
+///
+/// On Linux, the number of retired CPU instructions is read from the kernel's perf counters.
+/// If these are not available, the runner falls back to the work units that a solution reports via [`count`].
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::template::timings::Cost;
+#[cfg(feature = "cost-counters")]
+use crate::template::timings::CostUnit;
+
+static ITERATIONS: AtomicU64 = AtomicU64::new(0);
+
+/// Count `n` units of work, e.g. loop iterations of a hot loop.
+/// This is a no-op unless the `cost-counters` feature is active.
+#[inline]
+pub fn count(n: u64) {
+    if cfg!(feature = "cost-counters") {
+        ITERATIONS.fetch_add(n, Ordering::Relaxed);
+    }
+}
+
+/// Run `f` and measure its cost, if the `cost-counters` feature is active.
+#[cfg(feature = "cost-counters")]
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Cost>) {
+    let counter = perf::InstructionCounter::open();
+
+    if counter.is_none() {
+        static WARNING: std::sync::Once = std::sync::Once::new();
+        WARNING.call_once(|| {
+            eprintln!(
+                "Instruction counters are not available, falling back to counted iterations."
+            );
+        });
+    }
+
+    let before = ITERATIONS.load(Ordering::Relaxed);
+    let result = match &counter {
+        Some(counter) => counter.run(f),
+        None => (f(), None),
+    };
+    let iterations = ITERATIONS.load(Ordering::Relaxed) - before;
+
+    let cost = match result.1 {
+        Some(instructions) => Some(Cost {
+            value: instructions,
+            unit: CostUnit::Instructions,
+        }),
+        None if iterations > 0 => Some(Cost {
+            value: iterations,
+            unit: CostUnit::Iterations,
+        }),
+        None => None,
+    };
+
+    (result.0, cost)
+}
+
+#[cfg(not(feature = "cost-counters"))]
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Cost>) {
+    (f(), None)
+}
+
+#[cfg(all(feature = "cost-counters", target_os = "linux"))]
+mod perf {
+    use std::mem;
+
+    const PERF_TYPE_HARDWARE: u32 = 0;
+    const PERF_COUNT_HW_INSTRUCTIONS: u64 = 1;
+
+    const FLAG_DISABLED: u64 = 1 << 0;
+    const FLAG_EXCLUDE_KERNEL: u64 = 1 << 5;
+    const FLAG_EXCLUDE_HV: u64 = 1 << 6;
+
+    const PERF_EVENT_IOC_ENABLE: libc::c_ulong = 0x2400;
+    const PERF_EVENT_IOC_DISABLE: libc::c_ulong = 0x2401;
+    const PERF_EVENT_IOC_RESET: libc::c_ulong = 0x2403;
+
+    /// The first version of `struct perf_event_attr`, which every kernel accepts.
+    #[repr(C)]
+    #[derive(Default)]
+    struct PerfEventAttr {
+        kind: u32,
+        size: u32,
+        config: u64,
+        sample_period: u64,
+        sample_type: u64,
+        read_format: u64,
+        flags: u64,
+        wakeup_events: u32,
+        bp_type: u32,
+        config1: u64,
+    }
+
+    /// Counts user-space instructions retired by the current thread.
+    pub struct InstructionCounter {
+        fd: libc::c_int,
+    }
+
+    impl InstructionCounter {
+        pub fn open() -> Option<Self> {
+            let attr = PerfEventAttr {
+                kind: PERF_TYPE_HARDWARE,
+                size: u32::try_from(mem::size_of::<PerfEventAttr>()).ok()?,
+                config: PERF_COUNT_HW_INSTRUCTIONS,
+                flags: FLAG_DISABLED | FLAG_EXCLUDE_KERNEL | FLAG_EXCLUDE_HV,
+                ..PerfEventAttr::default()
+            };
+
+            // SAFETY: `attr` is a valid `perf_event_attr` that outlives the call.
+            let fd = unsafe {
+                libc::syscall(
+                    libc::SYS_perf_event_open,
+                    &attr as *const PerfEventAttr,
+                    0,
+                    -1,
+                    -1,
+                    0,
+                )
+            };
+
+            libc::c_int::try_from(fd)
+                .ok()
+                .filter(|fd| *fd >= 0)
+                .map(|fd| Self { fd })
+        }
+
+        /// Run `f` and return the number of instructions it retired.
+        pub fn run<T>(&self, f: impl FnOnce() -> T) -> (T, Option<u64>) {
+            // SAFETY: `self.fd` is an open perf event file descriptor.
+            unsafe {
+                libc::ioctl(self.fd, PERF_EVENT_IOC_RESET, 0);
+                libc::ioctl(self.fd, PERF_EVENT_IOC_ENABLE, 0);
+            }
+
+            let result = f();
+
+            let mut count: u64 = 0;
+            // SAFETY: `self.fd` is an open perf event file descriptor and `count` is a valid buffer of 8 bytes.
+            let read = unsafe {
+                libc::ioctl(self.fd, PERF_EVENT_IOC_DISABLE, 0);
+                libc::read(
+                    self.fd,
+                    (&raw mut count).cast::<libc::c_void>(),
+                    mem::size_of::<u64>(),
+                )
+            };
+
+            let count = usize::try_from(read)
+                .is_ok_and(|read| read == mem::size_of::<u64>())
+                .then_some(count);
+
+            (result, count)
+        }
+    }
+
+    impl Drop for InstructionCounter {
+        fn drop(&mut self) {
+            // SAFETY: `self.fd` is an open file descriptor owned by this counter.
+            unsafe {
+                libc::close(self.fd);
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "cost-counters", not(target_os = "linux")))]
+mod perf {
+    /// Instruction counters are only supported on Linux.
+    pub struct InstructionCounter;
+
+    impl InstructionCounter {
+        pub fn open() -> Option<Self> {
+            None
+        }
+
+        pub fn run<T>(&self, f: impl FnOnce() -> T) -> (T, Option<u64>) {
+            (f(), None)
+        }
+    }
+}
@@ -0,0 +1,99 @@
+/// Sampling CPU profiler for solution parts, enabled by the `cpu-profile` feature.
+/// Uses a signal-based in-process sampler and writes a folded-stack file and a flamegraph per part.
+use std::fmt::Write as _;
+use std::fs::{self, File};
+use std::hint::black_box;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use pprof::{ProfilerGuard, ProfilerGuardBuilder, Report};
+
+use crate::template::Day;
+
+/// Sampling frequency in Hz. A prime value avoids sampling in lockstep with periodic work.
+const FREQUENCY: i32 = 997;
+
+/// Paths of the files written for a profiled part.
+pub struct ProfileOutput {
+    pub folded: PathBuf,
+    pub flamegraph: PathBuf,
+}
+
+/// Run `func` repeatedly for at least `duration` and `min_iterations` runs while sampling the call stack,
+/// then write the results to the directory `dir`, see `paths.profiles` of the [`Config`](crate::template::Config).
+pub fn profile<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    duration: Duration,
+    min_iterations: u128,
+    day: Day,
+    part: u8,
+    dir: &str,
+) -> Result<ProfileOutput, String> {
+    let guard = start()?;
+    let timer = Instant::now();
+    let mut iterations = 0;
+
+    while iterations < min_iterations || timer.elapsed() < duration {
+        black_box(func(black_box(input.clone())));
+        iterations += 1;
+    }
+
+    let report = guard.report().build().map_err(|e| e.to_string())?;
+    drop(guard);
+
+    if report.data.is_empty() {
+        return Err("no samples were collected.".into());
+    }
+
+    write_report(&report, day, part, dir)
+}
+
+fn start() -> Result<ProfilerGuard<'static>, String> {
+    ProfilerGuardBuilder::default()
+        .frequency(FREQUENCY)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()
+        .map_err(|e| e.to_string())
+}
+
+/// Render a report in the folded-stack format consumed by flamegraph tools:
+/// one line per unique stack, frames separated by `;`, followed by the sample count.
+fn folded_stacks(report: &Report) -> String {
+    let mut lines: Vec<String> = report
+        .data
+        .iter()
+        .map(|(frames, count)| {
+            let mut line = frames.thread_name_or_id();
+
+            for frame in frames.frames.iter().rev() {
+                for symbol in frame.iter().rev() {
+                    let _ = write!(line, ";{symbol}");
+                }
+            }
+
+            let _ = write!(line, " {count}");
+            line
+        })
+        .collect();
+
+    lines.sort();
+    lines.join("\n")
+}
+
+fn write_report(report: &Report, day: Day, part: u8, dir: &str) -> Result<ProfileOutput, String> {
+    let dir = PathBuf::from(dir);
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    let output = ProfileOutput {
+        folded: dir.join(format!("{day}-part{part}.folded")),
+        flamegraph: dir.join(format!("{day}-part{part}.svg")),
+    };
+
+    fs::write(&output.folded, folded_stacks(report)).map_err(|e| e.to_string())?;
+
+    let file = File::create(&output.flamegraph).map_err(|e| e.to_string())?;
+    report.flamegraph(file).map_err(|e| e.to_string())?;
+
+    Ok(output)
+}
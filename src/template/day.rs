@@ -3,12 +3,10 @@ use std::fmt::Display;
 use std::str::FromStr;
 
 #[cfg(feature = "today")]
-use chrono::{Datelike, FixedOffset, Utc};
+use crate::template::{Clock, SystemClock};
 
-#[cfg(feature = "today")]
-const SERVER_UTC_OFFSET: i32 = -5;
-
-/// A valid day number of advent (i.e. an integer in range 1 to 25).
+/// A valid day number of advent (i.e. an integer in range 1 to [`Day::LIMIT`]).
+/// Whether a day belongs to the configured event is checked against [`crate::template::Config::max_day`].
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -22,10 +20,13 @@ const SERVER_UTC_OFFSET: i32 = -5;
 pub struct Day(u8);
 
 impl Day {
+    /// The largest day number an event can have, so that days display as two digits.
+    pub const LIMIT: u8 = 99;
+
     /// Creates a [`Day`] from the provided value if it's in the valid range,
     /// returns [`None`] otherwise.
     pub fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+        if day == 0 || day > Self::LIMIT {
             return None;
         }
         Some(Self(day))
@@ -45,15 +46,10 @@ impl Day {
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
-    pub fn today() -> Option<Self> {
-        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
-        let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
-            Self::new(u8::try_from(today.day()).ok()?)
-        } else {
-            None
-        }
+    /// Returns the current day if an event is running, `None` otherwise.
+    /// `utc_offset` and `days` are the server timezone and the configured number of puzzles, see [`puzzle_at`].
+    pub fn today(utc_offset: i32, days: Option<u8>) -> Option<Self> {
+        puzzle_at(SystemClock.now(), utc_offset, days).map(|(_, day)| day)
     }
 }
 
@@ -94,26 +90,26 @@ impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        write!(f, "expecting a day number between 1 and {}", Day::LIMIT)
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
-pub fn all_days() -> AllDays {
-    AllDays::new()
-}
-
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent from the 1st to the last day of an event.
+/// The days of the configured event are yielded by [`crate::template::Config::all_days`].
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
-    #[allow(clippy::new_without_default)]
-    pub fn new() -> Self {
-        Self { current: 1 }
+    /// Yields the days from the 1st to `last`, at most [`Day::LIMIT`].
+    pub fn until(last: u8) -> Self {
+        Self {
+            current: 1,
+            last: last.min(Day::LIMIT),
+        }
     }
 }
 
@@ -121,10 +117,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above the limit.
         let day = Day(self.current);
         self.current += 1;
 
@@ -134,31 +130,271 @@ impl Iterator for AllDays {
 
 /* -------------------------------------------------------------------------- */
 
+/// Number of days since the unix epoch for a date, e.g. `(2024, 12, 1)`.
+// see: http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+pub(crate) fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// The date `(year, month, day)` for a number of days since the unix epoch.
+// see: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Formats a unix timestamp as a UTC date, e.g. `2024-12-01`.
+pub(crate) fn format_date(timestamp: u64) -> String {
+    #[allow(clippy::cast_possible_wrap)]
+    let (year, month, day) = civil_from_days((timestamp / 86400) as i64);
+    format!("{year}-{month:02}-{day:02}")
+}
+
+/// Formats a number of seconds as a duration, e.g. `2d 03:04:05`.
+pub(crate) fn format_duration(seconds: i64) -> String {
+    let (days, rest) = (seconds / 86400, seconds % 86400);
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        rest / 3600,
+        (rest % 3600) / 60,
+        rest % 60
+    );
+
+    if days > 0 {
+        format!("{days}d {time}")
+    } else {
+        time
+    }
+}
+
+/// The weekday of the 1st of december in `year`, counting from monday as `0`.
+pub(crate) fn first_weekday_of_advent(year: i64) -> i64 {
+    // the unix epoch was a thursday.
+    (days_from_civil(year, 12, 1) + 3).rem_euclid(7)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Number of puzzles in the event of `year`: 25 until 2024, 12 since 2025.
+pub fn event_days(year: i64) -> u8 {
+    if year >= 2025 {
+        12
+    } else {
+        25
+    }
+}
+
+/// Unix timestamp at which the puzzle of `day` unlocks, i.e. midnight in the server timezone.
+pub fn unlock_time(year: i64, day: Day, utc_offset: i32) -> i64 {
+    days_from_civil(year, 12, i64::from(day.0)) * 86400 - i64::from(utc_offset) * 3600
+}
+
+/// The server date `(year, month, day)` at the unix timestamp `now`.
+fn server_date(now: i64, utc_offset: i32) -> (i64, i64, i64) {
+    civil_from_days((now + i64::from(utc_offset) * 3600).div_euclid(86400))
+}
+
+/// The year and day of the latest puzzle at the unix timestamp `now`, if an event is running.
+/// An event has `days` puzzles, i.e. the `days` key of the [`crate::template::Config`], or [`event_days`] if not set.
+pub fn puzzle_at(now: i64, utc_offset: i32, days: Option<u8>) -> Option<(i64, Day)> {
+    let (year, month, day) = server_date(now, utc_offset);
+    let day = u8::try_from(day).ok()?;
+    let last = days.unwrap_or_else(|| event_days(year));
+    (month == 12 && day <= last).then_some((year, Day(day)))
+}
+
+/// The year of the latest event that started at the unix timestamp `now`.
+pub fn latest_event(now: i64, utc_offset: i32) -> i64 {
+    match server_date(now, utc_offset) {
+        (year, 12, _) => year,
+        (year, _, _) => year - 1,
+    }
+}
+
+/// The year and day of the next puzzle to unlock after the unix timestamp `now`, with its unlock time.
+/// `days` is the number of puzzles of an event, see [`puzzle_at`].
+pub fn next_unlock(now: i64, utc_offset: i32, days: Option<u8>) -> (i64, Day, i64) {
+    let (year, day) = match puzzle_at(now, utc_offset, days) {
+        Some((year, day)) if day.0 < days.unwrap_or_else(|| event_days(year)) => {
+            (year, Day(day.0 + 1))
+        }
+        Some((year, _)) => (year + 1, Day(1)),
+        None => match server_date(now, utc_offset) {
+            (year, 12, _) => (year + 1, Day(1)),
+            (year, _, _) => (year, Day(1)),
+        },
+    };
+    (year, day, unlock_time(year, day, utc_offset))
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// Creates a [`Day`] value in a const context.
 #[macro_export]
 macro_rules! day {
     ($day:expr) => {{
         const _ASSERT: () = assert!(
-            $day != 0 && $day <= 25,
-            concat!(
-                "invalid day number `",
-                $day,
-                "`, expecting a value between 1 and 25"
-            ),
+            $day != 0 && $day <= $crate::template::Day::LIMIT,
+            "{}",
+            $crate::template::__InvalidDay::new($day).as_str(),
         );
         $crate::template::Day::__new_unchecked($day)
     }};
 }
 
+// Not part of the public API
+/// The message of the compile-time check in [`day!`], built from [`Day::LIMIT`].
+/// Const panics only accept a single string, so the message is formatted into a buffer.
+#[doc(hidden)]
+pub struct __InvalidDay {
+    buf: [u8; 64],
+    len: usize,
+}
+
+impl __InvalidDay {
+    pub const fn new(day: u8) -> Self {
+        Self {
+            buf: [0; 64],
+            len: 0,
+        }
+        .push_str("invalid day number `")
+        .push_number(day)
+        .push_str("`, expecting a value between 1 and ")
+        .push_number(Day::LIMIT)
+    }
+
+    const fn push_str(mut self, s: &str) -> Self {
+        let bytes = s.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            self.buf[self.len] = bytes[i];
+            self.len += 1;
+            i += 1;
+        }
+        self
+    }
+
+    const fn push_number(mut self, n: u8) -> Self {
+        let mut divisor = 100;
+        while divisor > 0 {
+            if n >= divisor || divisor == 1 {
+                self.buf[self.len] = b'0' + n / divisor % 10;
+                self.len += 1;
+            }
+            divisor /= 10;
+        }
+        self
+    }
+
+    pub const fn as_str(&self) -> &str {
+        match std::str::from_utf8(self.buf.split_at(self.len).0) {
+            Ok(s) => s,
+            Err(_) => "invalid day number",
+        }
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{
+        __InvalidDay, first_weekday_of_advent, format_date, format_duration, latest_event,
+        next_unlock, puzzle_at, unlock_time, AllDays, Day,
+    };
+
+    // 2024-12-01 00:00:00 UTC.
+    const DECEMBER_FIRST: i64 = 1_733_011_200;
+    const HOUR: i64 = 3600;
+
+    #[test]
+    fn formats_dates() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(1_733_011_200), "2024-12-01");
+        assert_eq!(format_date(1_709_164_800), "2024-02-29");
+    }
+
+    #[test]
+    fn formats_durations() {
+        assert_eq!(format_duration(59), "00:00:59");
+        assert_eq!(format_duration(3 * 3600 + 4 * 60 + 5), "03:04:05");
+        assert_eq!(format_duration(2 * 86400 + 61), "2d 00:01:01");
+    }
+
+    #[test]
+    fn computes_first_weekday_of_advent() {
+        // 2024-12-01 was a sunday, 2023-12-01 a friday.
+        assert_eq!(first_weekday_of_advent(2024), 6);
+        assert_eq!(first_weekday_of_advent(2023), 4);
+    }
+
+    #[test]
+    fn computes_unlock_times() {
+        assert_eq!(unlock_time(2024, Day(1), -5), DECEMBER_FIRST + 5 * HOUR);
+        assert_eq!(unlock_time(2024, Day(2), 0), DECEMBER_FIRST + 24 * HOUR);
+    }
+
+    #[test]
+    fn finds_current_puzzle() {
+        assert_eq!(puzzle_at(DECEMBER_FIRST, -5, None), None);
+        assert_eq!(
+            puzzle_at(DECEMBER_FIRST + 5 * HOUR, -5, None),
+            Some((2024, Day(1)))
+        );
+        assert_eq!(puzzle_at(DECEMBER_FIRST + 25 * 24 * HOUR, 0, None), None);
+        // 2025-12-12 and 2025-12-13.
+        assert_eq!(puzzle_at(1_765_497_600, 0, None), Some((2025, Day(12))));
+        assert_eq!(puzzle_at(1_765_584_000, 0, None), None);
+        // a configured number of puzzles overrides the length of the event.
+        assert_eq!(puzzle_at(1_765_584_000, 0, Some(25)), Some((2025, Day(13))));
+        assert_eq!(puzzle_at(DECEMBER_FIRST + 10 * 24 * HOUR, 0, Some(5)), None);
+    }
+
+    #[test]
+    fn finds_latest_event() {
+        assert_eq!(latest_event(DECEMBER_FIRST, -5), 2023);
+        assert_eq!(latest_event(DECEMBER_FIRST + 5 * HOUR, -5), 2024);
+        assert_eq!(latest_event(1_748_736_000, -5), 2024);
+    }
+
+    #[test]
+    fn finds_next_unlock() {
+        let (year, day, unlock) = next_unlock(DECEMBER_FIRST, -5, None);
+        assert_eq!(
+            (year, day, unlock),
+            (2024, Day(1), DECEMBER_FIRST + 5 * HOUR)
+        );
+
+        let (year, day, _) = next_unlock(DECEMBER_FIRST + 5 * HOUR, -5, None);
+        assert_eq!((year, day), (2024, Day(2)));
+
+        let (year, day, _) = next_unlock(DECEMBER_FIRST + 24 * 24 * HOUR, 0, None);
+        assert_eq!((year, day), (2025, Day(1)));
+
+        // 2025-06-01, and the last day of the shorter 2025 event.
+        assert_eq!(next_unlock(1_748_736_000, 0, None).1, Day(1));
+        let (year, day, _) = next_unlock(1_765_497_600, 0, None);
+        assert_eq!((year, day), (2026, Day(1)));
+        let (year, day, _) = next_unlock(1_765_497_600, 0, Some(25));
+        assert_eq!((year, day), (2025, Day(13)));
+    }
 
     #[test]
     fn all_days_iterator() {
-        let mut iter = all_days();
+        let mut iter = AllDays::until(25);
 
         assert_eq!(iter.next(), Some(Day(1)));
         assert_eq!(iter.next(), Some(Day(2)));
@@ -187,6 +423,26 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn shorter_events() {
+        assert_eq!(AllDays::until(12).last(), Some(Day(12)));
+        assert_eq!(AllDays::until(200).count(), 99);
+    }
+
+    #[test]
+    fn formats_invalid_day_message() {
+        assert_eq!(
+            __InvalidDay::new(100).as_str(),
+            format!(
+                "invalid day number `100`, expecting a value between 1 and {}",
+                Day::LIMIT
+            )
+        );
+        assert!(__InvalidDay::new(0)
+            .as_str()
+            .starts_with("invalid day number `0`,"));
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -0,0 +1,53 @@
+/// Debug output for solutions, printed with the [`debug!`](crate::debug) macro.
+///
+/// Output is enabled by passing `--verbose` to a solution or by setting `AOC_VERBOSE`, and goes to stderr
+/// so it does not mix with the answers and timings the runner reads from stdout.
+/// It is printed in debug and release builds, but never while a solution is benched with `--time`:
+///
+/// ```ignore
+/// advent_of_code::debug!("no solution found for {machine:?}");
+/// ```
+use std::{env, sync::OnceLock};
+
+/// Whether debug output was requested and the solution is not benched.
+/// Checked once, as `debug!` is commonly used in hot loops.
+pub fn is_enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+
+    *ENABLED.get_or_init(|| {
+        let requested = env::args().any(|x| x == "--verbose")
+            || env::var("AOC_VERBOSE").is_ok_and(|x| is_truthy(&x));
+        requested && !env::args().any(|x| x == "--time")
+    })
+}
+
+fn is_truthy(value: &str) -> bool {
+    !matches!(value, "" | "0" | "false")
+}
+
+/// Print a line to stderr if debug output is enabled, see [`crate::template::debug`].
+/// Takes the same arguments as `eprintln!`, which are only evaluated if the output is enabled.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::template::debug::is_enabled() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::is_truthy;
+
+    #[test]
+    fn reads_verbose_variable() {
+        assert!(is_truthy("1"));
+        assert!(is_truthy("true"));
+        assert!(!is_truthy(""));
+        assert!(!is_truthy("0"));
+        assert!(!is_truthy("false"));
+    }
+}
@@ -0,0 +1,255 @@
+/// Writes the frames of a [`Visualizer`](crate::template::visualize::Visualizer) to image files,
+/// enabled by the `visualize-export` feature.
+/// Frames are encoded as they are shown, either into an animated GIF or as a numbered sequence of PNG files.
+/// Each cell is drawn as a square block of pixels, captions are not part of the images.
+use std::{
+    borrow::Cow,
+    fs::{self, File},
+    io::BufWriter,
+    path::PathBuf,
+    time::Duration,
+};
+
+use crate::template::visualize::{Color, ExportFormat};
+
+/// Width and height of a cell in pixels.
+const CELL_SIZE: usize = 4;
+
+/// How a cell is drawn in an exported image.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Paint {
+    /// empty cells: `.` and spaces.
+    Background,
+    /// other characters without a colour.
+    Foreground,
+    Color(Color),
+}
+
+impl Paint {
+    /// Index of the paint in [`palette`].
+    fn index(self) -> u8 {
+        match self {
+            Paint::Background => 0,
+            Paint::Foreground => 1,
+            Paint::Color(color) => match color {
+                Color::Red => 2,
+                Color::Green => 3,
+                Color::Yellow => 4,
+                Color::Blue => 5,
+                Color::Magenta => 6,
+                Color::Cyan => 7,
+                Color::White => 8,
+                Color::Gray => 9,
+            },
+        }
+    }
+}
+
+/// RGB values of all paints, ordered by [`Paint::index`].
+fn palette() -> Vec<u8> {
+    [
+        [15, 15, 35],
+        [204, 204, 204],
+        [205, 49, 49],
+        [13, 188, 121],
+        [229, 229, 16],
+        [36, 114, 200],
+        [188, 63, 188],
+        [17, 168, 205],
+        [255, 255, 255],
+        [102, 102, 102],
+    ]
+    .concat()
+}
+
+/// Encodes frames to files below `dir`, named after `name`.
+pub struct Exporter {
+    format: ExportFormat,
+    dir: PathBuf,
+    name: String,
+    /// image size in pixels, taken from the first frame.
+    size: Option<(usize, usize)>,
+    gif: Option<gif::Encoder<BufWriter<File>>>,
+    frames: usize,
+}
+
+impl Exporter {
+    pub fn new(format: ExportFormat, dir: impl Into<PathBuf>, name: impl Into<String>) -> Self {
+        Self {
+            format,
+            dir: dir.into(),
+            name: name.into(),
+            size: None,
+            gif: None,
+            frames: 0,
+        }
+    }
+
+    /// The GIF file, or the directory of the PNG files.
+    pub fn path(&self) -> PathBuf {
+        match self.format {
+            ExportFormat::Gif => self.dir.join(format!("{}.gif", self.name)),
+            ExportFormat::Png => self.dir.join(&self.name),
+        }
+    }
+
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Encode a frame that is shown for `delay`.
+    pub fn write(&mut self, cells: &[Vec<Paint>], delay: Duration) -> Result<(), String> {
+        let (width, height) = *self.size.get_or_insert_with(|| {
+            let width = cells.iter().map(Vec::len).max().unwrap_or(0);
+            (width.max(1) * CELL_SIZE, cells.len().max(1) * CELL_SIZE)
+        });
+
+        let pixels = pixels(cells, width, height);
+        self.frames += 1;
+
+        match self.format {
+            ExportFormat::Gif => self.write_gif(&pixels, width, height, delay),
+            ExportFormat::Png => self.write_png(&pixels, width, height),
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn write_gif(
+        &mut self,
+        pixels: &[u8],
+        width: usize,
+        height: usize,
+        delay: Duration,
+    ) -> Result<(), String> {
+        let (Ok(width), Ok(height)) = (u16::try_from(width), u16::try_from(height)) else {
+            return Err(format!("{width}x{height} pixels are too large for a GIF."));
+        };
+
+        let mut encoder = match self.gif.take() {
+            Some(encoder) => encoder,
+            None => self.create_gif(width, height)?,
+        };
+
+        let frame = gif::Frame {
+            width,
+            height,
+            // in hundredths of a second, viewers slow down shorter delays.
+            delay: (delay.as_millis() / 10).clamp(2, u128::from(u16::MAX)) as u16,
+            buffer: Cow::Borrowed(pixels),
+            ..gif::Frame::default()
+        };
+
+        encoder.write_frame(&frame).map_err(|e| e.to_string())?;
+        self.gif = Some(encoder);
+        Ok(())
+    }
+
+    fn create_gif(&self, width: u16, height: u16) -> Result<gif::Encoder<BufWriter<File>>, String> {
+        fs::create_dir_all(&self.dir).map_err(|e| e.to_string())?;
+        let file = File::create(self.path()).map_err(|e| e.to_string())?;
+
+        let mut encoder = gif::Encoder::new(BufWriter::new(file), width, height, &palette())
+            .map_err(|e| e.to_string())?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(|e| e.to_string())?;
+        Ok(encoder)
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn write_png(&self, pixels: &[u8], width: usize, height: usize) -> Result<(), String> {
+        let dir = self.path();
+        fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+        let file =
+            File::create(dir.join(format!("{:05}.png", self.frames))).map_err(|e| e.to_string())?;
+
+        let mut encoder = png::Encoder::new(BufWriter::new(file), width as u32, height as u32);
+        encoder.set_color(png::ColorType::Indexed);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_palette(palette());
+
+        let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+        writer.write_image_data(pixels).map_err(|e| e.to_string())
+    }
+
+    /// Write the end of the GIF file. PNG files are complete once written.
+    pub fn finish(mut self) -> Result<PathBuf, String> {
+        if let Some(encoder) = self.gif.take() {
+            encoder
+                .into_inner()
+                .map_err(|e| e.to_string())?
+                .into_inner()
+                .map_err(|e| e.to_string())?;
+        }
+        Ok(self.path())
+    }
+}
+
+/// Palette indices of a `width` by `height` image, cells outside of the image are cut off.
+fn pixels(cells: &[Vec<Paint>], width: usize, height: usize) -> Vec<u8> {
+    let mut pixels = vec![Paint::Background.index(); width * height];
+
+    for (y, row) in cells.iter().enumerate().take(height / CELL_SIZE) {
+        for (x, paint) in row.iter().enumerate().take(width / CELL_SIZE) {
+            for py in y * CELL_SIZE..(y + 1) * CELL_SIZE {
+                let start = py * width + x * CELL_SIZE;
+                pixels[start..start + CELL_SIZE].fill(paint.index());
+            }
+        }
+    }
+
+    pixels
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{pixels, Exporter, Paint, CELL_SIZE};
+    use crate::template::test_server::temp_dir;
+    use crate::template::visualize::{Color, ExportFormat};
+    use std::{fs, time::Duration};
+
+    #[test]
+    fn scales_cells_to_pixels() {
+        let cells = vec![vec![Paint::Foreground, Paint::Color(Color::Red)]];
+        let pixels = pixels(&cells, 2 * CELL_SIZE, CELL_SIZE);
+
+        assert_eq!(pixels.len(), 2 * CELL_SIZE * CELL_SIZE);
+        assert_eq!(pixels[0], 1);
+        assert_eq!(pixels[CELL_SIZE], 2);
+        assert_eq!(pixels[pixels.len() - 1], 2);
+    }
+
+    #[test]
+    fn exports_gif_and_png() {
+        let dir = temp_dir("frame_export");
+        let cells = vec![vec![Paint::Background, Paint::Color(Color::Green)]; 3];
+
+        let mut gif = Exporter::new(ExportFormat::Gif, &dir, "06-part1");
+        gif.write(&cells, Duration::from_millis(50)).unwrap();
+        gif.write(&cells[..2], Duration::from_millis(50)).unwrap();
+        let path = gif.finish().unwrap();
+
+        let file = fs::File::open(&path).unwrap();
+        let mut decoder = gif::DecodeOptions::new().read_info(file).unwrap();
+        assert_eq!(
+            (decoder.width(), decoder.height()),
+            (2 * CELL_SIZE as u16, 3 * CELL_SIZE as u16)
+        );
+        let mut frames = 0;
+        while decoder.read_next_frame().unwrap().is_some() {
+            frames += 1;
+        }
+        assert_eq!(frames, 2);
+
+        let mut png = Exporter::new(ExportFormat::Png, &dir, "06-part2");
+        png.write(&cells, Duration::from_millis(50)).unwrap();
+        assert_eq!(png.frames(), 1);
+        let path = png.finish().unwrap();
+
+        let decoder = png::Decoder::new(fs::File::open(path.join("00001.png")).unwrap());
+        let reader = decoder.read_info().unwrap();
+        assert_eq!((reader.info().width, reader.info().height), (8, 12));
+    }
+}
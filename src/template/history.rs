@@ -0,0 +1,191 @@
+/// Stores every benchmark run written via `cargo time --store`, so timings can be compared over time.
+use std::{
+    collections::HashMap,
+    fs,
+    io::Error,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::read_if_exists;
+use crate::template::timings::{Timing, Timings};
+
+/// A single benchmark run.
+#[derive(Clone, Debug)]
+pub struct Run {
+    /// seconds since the unix epoch.
+    pub timestamp: u64,
+    pub timings: Timings,
+}
+
+/// All stored benchmark runs, oldest first.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub runs: Vec<Run>,
+}
+
+impl History {
+    /// Rehydrate the history from a JSON file at `path`. If not present, returns an empty history.
+    /// Errors if the file can not be read or parsed, so that it is not overwritten.
+    pub fn read_from_file(path: &str) -> Result<Self, String> {
+        match read_if_exists(path)? {
+            Some(s) => History::try_from(s).map_err(|e| format!("{path}: {e}")),
+            None => Ok(History::default()),
+        }
+    }
+
+    /// Dehydrate the history to a JSON file at `path`.
+    pub fn store_file(&self, path: &str) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Append a run that happened now.
+    pub fn push(&mut self, timings: Timings) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        self.runs.push(Run { timestamp, timings });
+    }
+
+    /// The timings of a day in each run that benched it, oldest first.
+    pub fn for_day(&self, day: crate::template::Day) -> Vec<(u64, &Timing)> {
+        self.runs
+            .iter()
+            .filter_map(|run| {
+                run.timings
+                    .data
+                    .iter()
+                    .find(|t| t.day == day)
+                    .map(|t| (run.timestamp, t))
+            })
+            .collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<History> for JsonValue {
+    fn from(value: History) -> Self {
+        let runs = value
+            .runs
+            .into_iter()
+            .map(|run| {
+                let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+                #[allow(clippy::cast_precision_loss)]
+                map.insert("timestamp".into(), JsonValue::Number(run.timestamp as f64));
+                map.insert(
+                    "data".into(),
+                    JsonValue::Array(run.timings.data.iter().map(JsonValue::from).collect()),
+                );
+
+                JsonValue::Object(map)
+            })
+            .collect();
+
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("runs".into(), JsonValue::Array(runs));
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for History {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let runs = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("runs")
+            .ok_or("expected JSON document to have key `runs`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.runs` to be an array.")?;
+
+        let runs = runs
+            .iter()
+            .map(|run| {
+                let run = run
+                    .get::<HashMap<String, JsonValue>>()
+                    .ok_or("Expected run to be a JSON object.")?;
+
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let timestamp = run
+                    .get("timestamp")
+                    .and_then(|v| v.get::<f64>())
+                    .map(|v| *v as u64)
+                    .ok_or("Expected run.timestamp to be a number.")?;
+
+                let data = run
+                    .get("data")
+                    .and_then(|v| v.get::<Vec<JsonValue>>())
+                    .ok_or("Expected run.data to be an array.")?
+                    .iter()
+                    .map(Timing::try_from)
+                    .collect::<Result<_, _>>()?;
+
+                Ok(Run {
+                    timestamp,
+                    timings: Timings { data },
+                })
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(History { runs })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::History;
+    use crate::day;
+    use crate::template::timings::{Timing, Timings};
+    use tinyjson::JsonValue;
+
+    fn timing(day: crate::template::Day, total_nanos: f64) -> Timing {
+        Timing {
+            day,
+            part_1: Some("1ms".into()),
+            part_2: None,
+            total_nanos,
+            part_1_heap: None,
+            part_2_heap: None,
+            part_1_allocs: None,
+            part_2_allocs: None,
+            part_1_cost: None,
+            part_2_cost: None,
+            parse: None,
+            part_1_samples: None,
+            part_2_samples: None,
+            part_1_stddev: None,
+            part_2_stddev: None,
+        }
+    }
+
+    #[test]
+    fn round_trips_history() {
+        let mut history = History::default();
+        history.push(Timings {
+            data: vec![timing(day!(1), 1e6), timing(day!(2), 2e6)],
+        });
+        history.push(Timings {
+            data: vec![timing(day!(1), 5e5)],
+        });
+
+        let json = JsonValue::from(history).stringify().unwrap();
+        let history = History::try_from(json).unwrap();
+
+        assert_eq!(history.runs.len(), 2);
+        assert_eq!(history.for_day(day!(1)).len(), 2);
+        assert_eq!(history.for_day(day!(2)).len(), 1);
+        assert_eq!(history.for_day(day!(1))[1].1.total_nanos, 5e5);
+        assert_eq!(history.for_day(day!(3)).len(), 0);
+    }
+}
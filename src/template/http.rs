@@ -0,0 +1,223 @@
+/// A minimal HTTP/1.1 client, used for the pages aoc-cli does not fetch: private leaderboards and personal stats.
+/// Plain `http://` urls are requested directly, `https://` urls with curl to avoid a TLS dependency.
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    process::{Command, Stdio},
+    time::Duration,
+};
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+const USER_AGENT: &str = "advent_of_code template";
+
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+pub fn get(url: &str, session: Option<&str>) -> Result<Response, String> {
+    if url.starts_with("https://") {
+        return curl(url, session);
+    }
+
+    let (address, host, path) = parse_url(url)?;
+
+    let mut stream =
+        TcpStream::connect(&address).map_err(|e| format!("could not connect to {host}: {e}"))?;
+    stream
+        .set_read_timeout(Some(TIMEOUT))
+        .and_then(|()| stream.set_write_timeout(Some(TIMEOUT)))
+        .map_err(|e| e.to_string())?;
+
+    let mut request = format!(
+        "GET {path} HTTP/1.1\r\nHost: {host}\r\nUser-Agent: {USER_AGENT}\r\nConnection: close\r\n"
+    );
+    if let Some(session) = session {
+        request.push_str(&format!("Cookie: session={session}\r\n"));
+    }
+    request.push_str("\r\n");
+
+    stream
+        .write_all(request.as_bytes())
+        .map_err(|e| format!("could not send request to {host}: {e}"))?;
+
+    let mut raw = vec![];
+    stream
+        .read_to_end(&mut raw)
+        .map_err(|e| format!("could not read response from {host}: {e}"))?;
+
+    parse_response(&raw)
+}
+
+/// Splits `http://host:port/path` into the address to connect to, the host header and the path.
+fn parse_url(url: &str) -> Result<(String, String, String), String> {
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or_else(|| format!("unsupported url \"{url}\", expected an http:// url."))?;
+
+    let (host, path) = match rest.find('/') {
+        Some(index) => (&rest[..index], &rest[index..]),
+        None => (rest, "/"),
+    };
+
+    if host.is_empty() {
+        return Err(format!("url \"{url}\" has no host."));
+    }
+
+    let address = if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{host}:80")
+    };
+
+    Ok((address, host.to_string(), path.to_string()))
+}
+
+fn curl(url: &str, session: Option<&str>) -> Result<Response, String> {
+    let mut command = Command::new("curl");
+    command.args([
+        "--silent",
+        "--show-error",
+        "--location",
+        "--max-time",
+        &TIMEOUT.as_secs().to_string(),
+        "--user-agent",
+        USER_AGENT,
+        // headers are read from stdin, so that the session does not show up in the process list.
+        "--header",
+        "@-",
+        "--write-out",
+        "\n%{http_code}",
+    ]);
+    let mut child = command
+        .arg(url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|_| "curl is needed to request https:// urls, but could not be run.")?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        if let Some(session) = session {
+            writeln!(stdin, "Cookie: session={session}").map_err(|e| e.to_string())?;
+        }
+    }
+
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(format!(
+            "curl failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (body, status) = stdout
+        .rsplit_once('\n')
+        .ok_or("malformed response: missing status.")?;
+
+    Ok(Response {
+        status: status
+            .trim()
+            .parse()
+            .map_err(|_| "malformed response: missing status.")?,
+        body: body.to_string(),
+    })
+}
+
+fn parse_response(raw: &[u8]) -> Result<Response, String> {
+    let split = raw
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .ok_or("malformed response: missing headers.")?;
+
+    let head = String::from_utf8_lossy(&raw[..split]);
+    let body = &raw[split + 4..];
+
+    let mut lines = head.lines();
+    let status = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|status| status.parse().ok())
+        .ok_or("malformed response: missing status.")?;
+
+    let is_chunked = lines.any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.trim().eq_ignore_ascii_case("transfer-encoding")
+                && value.trim().eq_ignore_ascii_case("chunked")
+        })
+    });
+
+    let body = if is_chunked {
+        decode_chunked(body)?
+    } else {
+        body.to_vec()
+    };
+
+    Ok(Response {
+        status,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+fn decode_chunked(mut raw: &[u8]) -> Result<Vec<u8>, String> {
+    let mut body = vec![];
+
+    loop {
+        let line_end = raw
+            .windows(2)
+            .position(|window| window == b"\r\n")
+            .ok_or("malformed chunked response.")?;
+        let size = String::from_utf8_lossy(&raw[..line_end]);
+        let size = usize::from_str_radix(size.split(';').next().unwrap_or_default().trim(), 16)
+            .map_err(|_| "malformed chunk size.")?;
+
+        if size == 0 {
+            return Ok(body);
+        }
+
+        let chunk = raw
+            .get(line_end + 2..line_end + 2 + size)
+            .ok_or("truncated chunked response.")?;
+        body.extend_from_slice(chunk);
+        raw = raw.get(line_end + 4 + size..).unwrap_or_default();
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_response, parse_url};
+
+    #[test]
+    fn parses_urls() {
+        assert_eq!(
+            parse_url("http://localhost:8080/2024/day/1").unwrap(),
+            (
+                "localhost:8080".into(),
+                "localhost:8080".into(),
+                "/2024/day/1".into()
+            )
+        );
+        assert_eq!(parse_url("http://example.com").unwrap().0, "example.com:80");
+        assert!(parse_url("https://adventofcode.com").is_err());
+    }
+
+    #[test]
+    fn parses_responses() {
+        let response =
+            parse_response(b"HTTP/1.1 404 Not Found\r\nContent-Length: 3\r\n\r\nnot").unwrap();
+        assert_eq!((response.status, response.body.as_str()), (404, "not"));
+
+        let chunked = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\nWiki\r\n5\r\npedia\r\n0\r\n\r\n";
+        assert_eq!(parse_response(chunked).unwrap().body, "Wikipedia");
+    }
+}
@@ -0,0 +1,156 @@
+use std::io::{self, Read};
+use std::{env, fs};
+
+use crate::template::{Config, Day};
+
+/// The input a solution binary is run against.
+///
+/// Solution binaries receive the source via their command-line arguments:
+///  - no argument: the puzzle input `<inputs>/<day>.txt`.
+///  - `--input <path>`: an arbitrary file, `--input -` reads from stdin.
+///  - `--example [name]`: the example `<examples>/<day>.txt`, or `<examples>/<day>-<name>.txt` if a name is given.
+///
+/// The `inputs` and `examples` directories are set in the [`Config`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Puzzle,
+    File(String),
+    Stdin,
+    Example(Option<String>),
+}
+
+impl InputSource {
+    /// Determine the input source from a list of command-line arguments.
+    pub fn from_args(args: &[String]) -> Self {
+        if let Some(index) = args.iter().position(|x| x == "--input") {
+            return match args.get(index + 1).map(String::as_str) {
+                Some("-") => Self::Stdin,
+                Some(path) => Self::File(path.to_string()),
+                None => {
+                    eprintln!(
+                        "Unexpected command-line input. Format: cargo solve 1 --input <path>"
+                    );
+                    std::process::exit(1);
+                }
+            };
+        }
+
+        if let Some(index) = args.iter().position(|x| x == "--example") {
+            let name = args
+                .get(index + 1)
+                .filter(|name| !name.starts_with("--"))
+                .cloned();
+            return Self::Example(name);
+        }
+
+        if let Some(name) = args.iter().find_map(|x| x.strip_prefix("--example=")) {
+            return Self::Example(Some(name.to_string()));
+        }
+
+        Self::Puzzle
+    }
+
+    /// Determine the input source from the arguments of the running process.
+    pub fn from_env() -> Self {
+        Self::from_args(&env::args().collect::<Vec<_>>())
+    }
+
+    /// Convert the input source back into command-line arguments for a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Self::Puzzle => vec![],
+            Self::File(path) => vec!["--input".into(), path.clone()],
+            Self::Stdin => vec!["--input".into(), "-".into()],
+            Self::Example(None) => vec!["--example".into()],
+            Self::Example(Some(name)) => vec!["--example".into(), name.clone()],
+        }
+    }
+
+    /// Read the input for a day from this source.
+    pub fn read(&self, config: &Config, day: Day) -> String {
+        match self {
+            Self::Puzzle => {
+                fs::read_to_string(config.input_path(day)).expect("could not open input file")
+            }
+            Self::Example(None) => {
+                fs::read_to_string(config.example_path(day)).expect("could not open example file")
+            }
+            Self::Example(Some(name)) => {
+                let filepath = format!("{}/{day}-{name}.txt", config.paths.examples);
+                fs::read_to_string(filepath).expect("could not open example file")
+            }
+            Self::File(path) => fs::read_to_string(path).expect("could not open input file"),
+            Self::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .expect("could not read input from stdin");
+                input
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::InputSource;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn defaults_to_puzzle_input() {
+        assert_eq!(
+            InputSource::from_args(&args("01 --time")),
+            InputSource::Puzzle
+        );
+    }
+
+    #[test]
+    fn parses_input_path() {
+        assert_eq!(
+            InputSource::from_args(&args("01 --input other.txt --time")),
+            InputSource::File("other.txt".into())
+        );
+        assert_eq!(
+            InputSource::from_args(&args("01 --input -")),
+            InputSource::Stdin
+        );
+    }
+
+    #[test]
+    fn parses_example_with_optional_name() {
+        assert_eq!(
+            InputSource::from_args(&args("01 --example")),
+            InputSource::Example(None)
+        );
+        assert_eq!(
+            InputSource::from_args(&args("01 --example --time")),
+            InputSource::Example(None)
+        );
+        assert_eq!(
+            InputSource::from_args(&args("01 --example 2")),
+            InputSource::Example(Some("2".into()))
+        );
+        assert_eq!(
+            InputSource::from_args(&args("01 --example=large --time")),
+            InputSource::Example(Some("large".into()))
+        );
+    }
+
+    #[test]
+    fn round_trips_args() {
+        for source in [
+            InputSource::Puzzle,
+            InputSource::File("a.txt".into()),
+            InputSource::Stdin,
+            InputSource::Example(None),
+            InputSource::Example(Some("large".into())),
+        ] {
+            assert_eq!(InputSource::from_args(&source.to_args()), source);
+        }
+    }
+}
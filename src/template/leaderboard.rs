@@ -0,0 +1,254 @@
+/// Private leaderboards, fetched from `/<year>/leaderboard/private/view/<id>.json` and cached on disk.
+/// The server asks to not request a leaderboard more often than every 15 minutes, so a cached copy is reused until then.
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::Path,
+    str::FromStr,
+    time::UNIX_EPOCH,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{aoc_cli, Clock, Config, Day};
+
+/// Seconds a fetched leaderboard is reused for.
+pub const CACHE_DURATION: u64 = 15 * 60;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    /// unix timestamps at which the stars of a day were earned, by part.
+    pub completions: BTreeMap<Day, [Option<u64>; 2]>,
+}
+
+impl Member {
+    /// The name shown on the website, which hides the ids of anonymous users.
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Leaderboard {
+    /// members by descending local score.
+    pub members: Vec<Member>,
+}
+
+/// A leaderboard and the unix timestamp it was fetched at.
+pub struct Fetched {
+    pub leaderboard: Leaderboard,
+    pub timestamp: u64,
+    /// whether the server could not be reached and an outdated copy is used.
+    pub is_stale: bool,
+}
+
+fn cache_path(config: &Config, year: i64, id: &str) -> String {
+    format!("{}/leaderboard_{year}_{id}.json", config.paths.cache)
+}
+
+/// Unix timestamp a file was last written at.
+fn modified_at(path: &str) -> Option<u64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_secs())
+}
+
+/// Fetch the leaderboard `id` of the event `year`, reusing the cached copy while it is fresh.
+pub fn fetch(config: &Config, year: i64, id: &str, clock: &impl Clock) -> Result<Fetched, String> {
+    let path = cache_path(config, year, id);
+    let now = u64::try_from(clock.now()).unwrap_or(0);
+    let cached = modified_at(&path).zip(fs::read_to_string(&path).ok());
+
+    if let Some((timestamp, json)) = &cached {
+        if now.saturating_sub(*timestamp) < CACHE_DURATION {
+            return Ok(Fetched {
+                leaderboard: Leaderboard::try_from(json.as_str())?,
+                timestamp: *timestamp,
+                is_stale: false,
+            });
+        }
+    }
+
+    match download(config, year, id) {
+        Ok(json) => {
+            let leaderboard = Leaderboard::try_from(json.as_str())?;
+            let stored = Path::new(&path)
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|()| fs::write(&path, &json));
+            if let Err(e) = stored {
+                eprintln!("Failed to cache the leaderboard: {e}");
+            }
+            Ok(Fetched {
+                leaderboard,
+                timestamp: now,
+                is_stale: false,
+            })
+        }
+        Err(e) => match cached {
+            Some((timestamp, json)) => {
+                eprintln!("{e}");
+                Ok(Fetched {
+                    leaderboard: Leaderboard::try_from(json.as_str())?,
+                    timestamp,
+                    is_stale: true,
+                })
+            }
+            None => Err(e),
+        },
+    }
+}
+
+fn download(config: &Config, year: i64, id: &str) -> Result<String, String> {
+    aoc_cli::fetch_authenticated(
+        config,
+        &format!("/{year}/leaderboard/private/view/{id}.json"),
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl TryFrom<&str> for Leaderboard {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let error =
+            "could not parse the leaderboard, is the session cookie valid and the id correct?";
+
+        let json = JsonValue::from_str(value).map_err(|_| error)?;
+        let members = json
+            .get::<HashMap<String, JsonValue>>()
+            .and_then(|json| json.get("members"))
+            .and_then(|members| members.get::<HashMap<String, JsonValue>>())
+            .ok_or(error)?;
+
+        let mut members = members
+            .iter()
+            .map(|(id, member)| Member::try_from((id.as_str(), member)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(b.stars.cmp(&a.stars))
+                .then(a.id.cmp(&b.id))
+        });
+
+        Ok(Leaderboard { members })
+    }
+}
+
+impl TryFrom<(&str, &JsonValue)> for Member {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from((id, value): (&str, &JsonValue)) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected leaderboard member to be a JSON object.")?;
+
+        let number = |json: &HashMap<String, JsonValue>, key: &str| {
+            json.get(key).and_then(|v| v.get::<f64>()).copied()
+        };
+
+        let mut completions = BTreeMap::new();
+        if let Some(days) = json
+            .get("completion_day_level")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+        {
+            for (day, parts) in days {
+                let Some(day) = day.parse().ok().and_then(Day::new) else {
+                    continue;
+                };
+                let Some(parts) = parts.get::<HashMap<String, JsonValue>>() else {
+                    continue;
+                };
+
+                let star = |part: &str| {
+                    parts
+                        .get(part)
+                        .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+                        .and_then(|part| number(part, "get_star_ts"))
+                        .map(|ts| ts as u64)
+                };
+                completions.insert(day, [star("1"), star("2")]);
+            }
+        }
+
+        Ok(Member {
+            id: id
+                .parse()
+                .map_err(|_| format!("expected member id `{id}` to be a number."))?,
+            name: json.get("name").and_then(|v| v.get::<String>()).cloned(),
+            stars: number(json, "stars").unwrap_or(0.0) as u32,
+            local_score: number(json, "local_score").unwrap_or(0.0) as u32,
+            completions,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{fetch, Leaderboard, CACHE_DURATION};
+    use crate::day;
+    use crate::template::test_server::{TestServer, LEADERBOARD};
+    use crate::template::{Clock, Config, SystemClock};
+    use std::time::Duration;
+
+    struct Later(u64);
+
+    impl Clock for Later {
+        fn now(&self) -> i64 {
+            SystemClock.now() + i64::try_from(self.0).unwrap()
+        }
+
+        fn sleep(&self, _: Duration) {}
+    }
+
+    #[test]
+    fn parses_leaderboard() {
+        let leaderboard = Leaderboard::try_from(LEADERBOARD).unwrap();
+        let names: Vec<String> = leaderboard
+            .members
+            .iter()
+            .map(super::Member::display_name)
+            .collect();
+
+        assert_eq!(names, vec!["Alice", "(anonymous user #3)", "Bob"]);
+        assert_eq!(leaderboard.members[0].local_score, 6);
+        assert_eq!(
+            leaderboard.members[0].completions.get(&day!(1)),
+            Some(&[Some(1_733_029_261), Some(1_733_029_500)])
+        );
+        assert!(Leaderboard::try_from("<html>log in</html>").is_err());
+    }
+
+    #[test]
+    fn caches_leaderboard() {
+        let (server, dir) = TestServer::with_temp_dir("leaderboard");
+
+        let mut config = Config::default();
+        config.server.url.clone_from(&server.url);
+        config.paths.cache = dir.to_string_lossy().into();
+
+        let fetched = fetch(&config, 2024, "123", &SystemClock).unwrap();
+        assert_eq!(fetched.leaderboard.members.len(), 3);
+        fetch(&config, 2024, "123", &SystemClock).unwrap();
+        assert_eq!(server.requests().len(), 1);
+
+        fetch(&config, 2024, "123", &Later(CACHE_DURATION + 1)).unwrap();
+        assert_eq!(
+            server.requests(),
+            vec![
+                "GET /2024/leaderboard/private/view/123.json",
+                "GET /2024/leaderboard/private/view/123.json"
+            ]
+        );
+    }
+}
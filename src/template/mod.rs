@@ -1,25 +1,71 @@
-use std::{env, fs};
+use std::{env, fs, io, path::PathBuf};
 
+pub mod alloc;
 pub mod aoc_cli;
+pub mod cli;
 pub mod commands;
+pub mod cost;
+#[cfg(feature = "cpu-profile")]
+pub mod cpu_profile;
+pub mod debug;
+#[cfg(feature = "visualize-export")]
+pub mod frame_export;
 pub mod runner;
+pub mod visualize;
 
+pub use clock::{Clock, SystemClock};
+pub use config::Config;
 pub use day::*;
+pub use input::*;
+pub use run_multi::RunOptions;
 
+mod clock;
+mod config;
 mod day;
+mod history;
+mod http;
+mod input;
+mod leaderboard;
+mod progress;
+mod puzzle;
 mod readme_benchmarks;
 mod run_multi;
+mod stats;
+mod submission;
+#[cfg(feature = "test_lib")]
+mod test_server;
 mod timings;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// The directory of a data folder, `inputs`, `examples` and `puzzles` are resolved via the [`Config`].
+fn data_dir(folder: &str) -> PathBuf {
+    let config = Config::current();
+    let dir = match folder {
+        "inputs" => config.paths.inputs.clone(),
+        "examples" => config.paths.examples.clone(),
+        "puzzles" => config.paths.puzzles.clone(),
+        folder => format!("data/{folder}"),
+    };
+    env::current_dir().unwrap().join(dir)
+}
+
+/// Read a file that may not exist yet, e.g. stored timings. Other errors are returned,
+/// so that callers do not overwrite a file they could not read.
+pub(crate) fn read_if_exists(path: &str) -> Result<Option<String>, String> {
+    match fs::read_to_string(path) {
+        Ok(s) => Ok(Some(s)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("{path}: {e}")),
+    }
+}
+
 /// Helper function that reads a text file to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = data_dir(folder).join(format!("{day}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
@@ -27,11 +73,7 @@ pub fn read_file(folder: &str, day: Day) -> String {
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+    let filepath = data_dir(folder).join(format!("{day}-{part}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
@@ -39,11 +81,16 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Alternatively, `parse = <function>` names a parse function that is timed separately from the parts.
+/// The parts then receive a reference to the parsed input instead of the raw input.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
         $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
     };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, parse = $parse, [part_one, 1] [part_two, 2]);
+    };
     ($day:expr, 1) => {
         $crate::solution!(@impl $day, [part_one, 1]);
     };
@@ -51,7 +98,7 @@ macro_rules! solution {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $day:expr, $( parse = $parse:expr, )? $( [$func:expr, $part:expr] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -59,10 +106,16 @@ macro_rules! solution {
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        #[cfg(all(feature = "count-allocs", not(feature = "dhat-heap")))]
+        #[global_allocator]
+        static ALLOC: $crate::template::alloc::CountingAlloc = $crate::template::alloc::CountingAlloc;
+
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let config = $crate::template::Config::current();
+            let input = $crate::template::InputSource::from_env().read(config, DAY);
+            $( let input = run_parse($parse, &input); )?
+            $( run_part($func, &input, DAY, $part, config); )*
         }
     };
 }
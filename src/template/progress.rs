@@ -0,0 +1,297 @@
+/// Which parts were solved, when, after how many attempts and with which answer.
+/// Derived from the log of answers submitted via `cargo solve <day> --submit <part>`.
+use crate::template::submission::Submission;
+use crate::template::{format_date, Day};
+
+/// Progress of a single part.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PartProgress {
+    pub solved: bool,
+    /// date the part was solved, e.g. `2024-12-01`.
+    pub date: Option<String>,
+    /// number of answers submitted, including the correct one.
+    pub attempts: u32,
+    /// the correct answer, once solved.
+    pub answer: Option<String>,
+}
+
+/// Progress of a single day of an event.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayProgress {
+    /// year of the event, `None` for submissions made without a configured year.
+    pub year: Option<u16>,
+    pub day: Day,
+    pub parts: [PartProgress; 2],
+}
+
+impl DayProgress {
+    /// Number of solved parts.
+    pub fn stars(&self) -> usize {
+        self.parts.iter().filter(|part| part.solved).count()
+    }
+}
+
+/// The verdict on a submitted answer, as reported by the server.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    Correct,
+    Incorrect,
+    /// the part was already solved before.
+    AlreadySolved,
+    /// the answer was not checked, e.g. because of the submission cooldown.
+    Unchecked,
+}
+
+impl SubmissionOutcome {
+    /// Parse the verdict from the response text of a submission.
+    pub fn parse(response: &str) -> Self {
+        if response.contains("That's the right answer") {
+            SubmissionOutcome::Correct
+        } else if response.contains("That's not the right answer") {
+            SubmissionOutcome::Incorrect
+        } else if response.contains("Did you already complete it") {
+            SubmissionOutcome::AlreadySolved
+        } else {
+            SubmissionOutcome::Unchecked
+        }
+    }
+}
+
+/// Progress for all days of the event.
+#[derive(Clone, Debug, Default)]
+pub struct Progress {
+    pub days: Vec<DayProgress>,
+}
+
+impl Progress {
+    /// Replay the log of submitted answers, in the order they were submitted.
+    pub fn from_log(submissions: &[Submission]) -> Self {
+        let mut progress = Progress::default();
+        for submission in submissions {
+            progress.record(
+                submission.year,
+                submission.day,
+                submission.part,
+                &submission.answer,
+                SubmissionOutcome::parse(&submission.response),
+                &format_date(submission.timestamp),
+            );
+        }
+        progress
+    }
+
+    /// Only the progress of the given `days` of the event of `year`.
+    pub fn event(&self, year: Option<u16>, days: impl IntoIterator<Item = Day>) -> Self {
+        let days: Vec<Day> = days.into_iter().collect();
+        Progress {
+            days: self
+                .days
+                .iter()
+                .filter(|d| d.year == year && days.contains(&d.day))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    pub fn get(&self, year: Option<u16>, day: Day) -> Option<&DayProgress> {
+        self.days.iter().find(|d| d.year == year && d.day == day)
+    }
+
+    /// Total number of solved parts.
+    pub fn stars(&self) -> usize {
+        self.days.iter().map(DayProgress::stars).sum()
+    }
+
+    /// Record a submitted answer. `date` is the date of the submission.
+    pub fn record(
+        &mut self,
+        year: Option<u16>,
+        day: Day,
+        part: u8,
+        answer: &str,
+        outcome: SubmissionOutcome,
+        date: &str,
+    ) {
+        let position =
+            |days: &[DayProgress]| days.iter().position(|d| d.year == year && d.day == day);
+        let index = match position(&self.days) {
+            Some(index) => index,
+            None => {
+                self.days.push(DayProgress {
+                    year,
+                    day,
+                    parts: [PartProgress::default(), PartProgress::default()],
+                });
+                self.days.sort_unstable_by_key(|d| (d.year, d.day));
+                position(&self.days).unwrap()
+            }
+        };
+
+        let Some(progress) = self.days[index].parts.get_mut(usize::from(part) - 1) else {
+            return;
+        };
+
+        match outcome {
+            SubmissionOutcome::Correct => {
+                progress.attempts += 1;
+                progress.solved = true;
+                progress.date = Some(date.into());
+                progress.answer = Some(answer.into());
+            }
+            SubmissionOutcome::Incorrect => progress.attempts += 1,
+            // the date and answer of an earlier solve are unknown, keep them if they were recorded.
+            SubmissionOutcome::AlreadySolved => progress.solved = true,
+            SubmissionOutcome::Unchecked => {}
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PartProgress, Progress, SubmissionOutcome};
+    use crate::day;
+    use crate::template::submission::Submission;
+    use crate::template::AllDays;
+
+    #[test]
+    fn parses_submission_outcomes() {
+        assert_eq!(
+            SubmissionOutcome::parse(
+                "That's the right answer! You are one gold star closer to saving your vacation."
+            ),
+            SubmissionOutcome::Correct
+        );
+        assert_eq!(
+            SubmissionOutcome::parse("That's not the right answer; your answer is too low."),
+            SubmissionOutcome::Incorrect
+        );
+        assert_eq!(
+            SubmissionOutcome::parse(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ),
+            SubmissionOutcome::AlreadySolved
+        );
+        assert_eq!(
+            SubmissionOutcome::parse("You gave an answer too recently; you have to wait after submitting an answer before trying again."),
+            SubmissionOutcome::Unchecked
+        );
+    }
+
+    #[test]
+    fn records_submissions() {
+        let mut progress = Progress::default();
+        progress.record(
+            Some(2024),
+            day!(3),
+            1,
+            "41",
+            SubmissionOutcome::Incorrect,
+            "2024-12-03",
+        );
+        progress.record(
+            Some(2024),
+            day!(3),
+            1,
+            "41",
+            SubmissionOutcome::Unchecked,
+            "2024-12-03",
+        );
+        progress.record(
+            Some(2024),
+            day!(3),
+            1,
+            "42",
+            SubmissionOutcome::Correct,
+            "2024-12-04",
+        );
+        progress.record(
+            Some(2024),
+            day!(1),
+            2,
+            "7",
+            SubmissionOutcome::AlreadySolved,
+            "2024-12-04",
+        );
+
+        assert_eq!(progress.days[0].day, day!(1));
+        assert_eq!(progress.stars(), 2);
+
+        let part = &progress.get(Some(2024), day!(3)).unwrap().parts[0];
+        assert_eq!(part.attempts, 2);
+        assert_eq!(part.date.as_deref(), Some("2024-12-04"));
+        assert_eq!(part.answer.as_deref(), Some("42"));
+
+        let part = &progress.get(Some(2024), day!(1)).unwrap().parts[1];
+        assert!(part.solved);
+        assert_eq!(part.answer, None);
+    }
+
+    #[test]
+    fn keeps_events_apart() {
+        let mut progress = Progress::default();
+        progress.record(
+            Some(2024),
+            day!(1),
+            1,
+            "1",
+            SubmissionOutcome::Correct,
+            "2024-12-01",
+        );
+        progress.record(
+            Some(2025),
+            day!(1),
+            1,
+            "2",
+            SubmissionOutcome::Correct,
+            "2025-12-01",
+        );
+        progress.record(
+            Some(2025),
+            day!(13),
+            1,
+            "3",
+            SubmissionOutcome::Correct,
+            "2025-12-13",
+        );
+
+        assert_eq!(progress.stars(), 3);
+        assert_eq!(
+            progress.get(Some(2024), day!(1)).unwrap().parts[0]
+                .answer
+                .as_deref(),
+            Some("1")
+        );
+        assert_eq!(progress.get(None, day!(1)), None);
+
+        let event = progress.event(Some(2025), AllDays::until(12));
+        assert_eq!(event.stars(), 1);
+        assert_eq!(event.days[0].parts[0].answer.as_deref(), Some("2"));
+    }
+
+    #[test]
+    fn replays_submission_log() {
+        let submission = |part: u8, answer: &str, timestamp: u64, response: &str| Submission {
+            year: Some(2024),
+            day: day!(1),
+            part,
+            answer: answer.into(),
+            timestamp,
+            response: response.into(),
+        };
+
+        let progress = Progress::from_log(&[
+            submission(1, "41", 1_733_011_200, "That's not the right answer."),
+            submission(1, "42", 1_733_100_000, "That's the right answer!"),
+            submission(2, "7", 1_733_100_000, "You gave an answer too recently."),
+        ]);
+
+        assert_eq!(progress.stars(), 1);
+        let parts = &progress.get(Some(2024), day!(1)).unwrap().parts;
+        assert_eq!(parts[0].attempts, 2);
+        assert_eq!(parts[0].date.as_deref(), Some("2024-12-02"));
+        assert_eq!(parts[0].answer.as_deref(), Some("42"));
+        assert_eq!(parts[1], PartProgress::default());
+    }
+}
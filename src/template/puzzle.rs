@@ -0,0 +1,151 @@
+/// Extracts information from the puzzle descriptions written by `cargo download`.
+/// The descriptions are markdown converted from the puzzle page, so extraction is a best-effort heuristic:
+///  - the title is read from the `--- Day 1: <title> ---` heading.
+///  - examples are the fenced code blocks of the first part.
+///  - the example answer of a part is the last emphasized code span of its section, e.g. `*11*`.
+use std::fs;
+
+use crate::template::{Config, Day};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Puzzle {
+    pub title: Option<String>,
+    pub examples: Vec<String>,
+    pub answers: [Option<String>; 2],
+}
+
+impl Puzzle {
+    /// Read the description of a day, if it was downloaded.
+    pub fn read(config: &Config, day: Day) -> Option<Self> {
+        let markdown = fs::read_to_string(config.puzzle_path(day)).ok()?;
+        Some(Self::parse(&markdown))
+    }
+
+    pub fn parse(markdown: &str) -> Self {
+        let (part_one, part_two) = match markdown.find("--- Part Two ---") {
+            Some(index) => (&markdown[..index], Some(&markdown[index..])),
+            None => (markdown, None),
+        };
+
+        Puzzle {
+            title: parse_title(markdown),
+            examples: code_blocks(part_one),
+            answers: [
+                emphasized_code(part_one).pop(),
+                part_two.and_then(|part| emphasized_code(part).pop()),
+            ],
+        }
+    }
+}
+
+fn parse_title(markdown: &str) -> Option<String> {
+    let line = markdown.lines().find(|line| line.contains("--- Day "))?;
+    let (_, title) = line.split_once(": ")?;
+    let title = title.trim().trim_end_matches('-').trim();
+    (!title.is_empty()).then(|| title.replace('\\', ""))
+}
+
+/// Contents of fenced code blocks.
+fn code_blocks(markdown: &str) -> Vec<String> {
+    let mut blocks = vec![];
+    let mut current: Option<Vec<&str>> = None;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            match current.take() {
+                Some(lines) => blocks.push(lines.join("\n") + "\n"),
+                None => current = Some(vec![]),
+            }
+        } else if let Some(lines) = current.as_mut() {
+            lines.push(line);
+        }
+    }
+
+    blocks
+}
+
+/// Inline code spans that are emphasized, either `` `*x*` `` or ``*`x`*``.
+fn emphasized_code(markdown: &str) -> Vec<String> {
+    let mut spans = vec![];
+    let mut in_fence = false;
+
+    for line in markdown.lines() {
+        // skip fenced code blocks, including their contents.
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        let parts: Vec<&str> = line.split('`').collect();
+
+        // code spans are the odd parts between two backticks.
+        for index in (1..parts.len().saturating_sub(1)).step_by(2) {
+            let code = parts[index];
+
+            let inner = code
+                .strip_prefix('*')
+                .and_then(|x| x.strip_suffix('*'))
+                .filter(|x| !x.is_empty());
+
+            let is_wrapped = parts[index - 1].ends_with('*') && parts[index + 1].starts_with('*');
+
+            match inner {
+                Some(inner) => spans.push(inner.to_string()),
+                None if is_wrapped && !code.is_empty() => spans.push(code.to_string()),
+                None => {}
+            }
+        }
+    }
+
+    spans
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{emphasized_code, Puzzle};
+
+    const PUZZLE: &str = r"\--- Day 1: Historian Hysteria ---
+----------
+
+For example:
+
+```
+3   4
+4   3
+```
+
+Pair up the smallest number, the distance is `*2*`.
+
+In the example above, this is `*11*`!
+
+\--- Part Two ---
+----------
+
+So, for these example lists, the similarity score at the end of this process is *`31`*.
+";
+
+    #[test]
+    fn parses_puzzle() {
+        let puzzle = Puzzle::parse(PUZZLE);
+        assert_eq!(puzzle.title.as_deref(), Some("Historian Hysteria"));
+        assert_eq!(puzzle.examples, vec!["3   4\n4   3\n".to_string()]);
+        assert_eq!(puzzle.answers, [Some("11".into()), Some("31".into())]);
+    }
+
+    #[test]
+    fn skips_code_in_fenced_blocks() {
+        let markdown = "The answer is `*7*`.\n\n```\nnot an answer: `*99*`\n```\n";
+        assert_eq!(emphasized_code(markdown), vec!["7".to_string()]);
+    }
+
+    #[test]
+    fn handles_missing_parts() {
+        let puzzle = Puzzle::parse("no heading, no examples, only `code`.");
+        assert_eq!(puzzle, Puzzle::default());
+    }
+}
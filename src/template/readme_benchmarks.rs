@@ -1,11 +1,16 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+///
+/// By default, a single table is rendered between two `<!--- benchmarking table --->` markers.
+/// The layout and the number of tables can be configured in `aoc.toml`, see [`TableConfig`].
+/// Columns can also show the personal stats imported with `cargo stats`.
+/// The marker and the paths of the files are set in the [`Config`].
+use std::{fs, io, ops::Range, str::FromStr};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
-
-static MARKER: &str = "<!--- benchmarking table --->";
+use crate::template::alloc::AllocCount;
+use crate::template::stats::{DayStats, Stats};
+use crate::template::timings::{format_bytes, HeapSummary, Timing, Timings};
+use crate::template::{event_days, AllDays, Clock, Config, Day, SystemClock};
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -25,82 +30,480 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+impl TablePosition {
+    /// The range from the start of the opening marker to the end of the closing marker.
+    pub fn range(&self) -> Range<usize> {
+        self.pos_start..self.pos_end
+    }
+}
+
+/// Relative link to the source of a day, e.g. `./src/bin/01.rs`.
+fn source_link(bin: &str, day: Day) -> String {
+    let path = format!("{bin}/{day}.rs");
+    if path.starts_with("./") || path.starts_with('/') {
+        path
+    } else {
+        format!("./{path}")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A column of the benchmark table.
+/// Columns that show data for each part render as `part 1 / part 2`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    Day,
+    Part1,
+    Part2,
+    Parse,
+    Samples,
+    Stddev,
+    Heap,
+    Allocations,
+    Cost,
+    Stars,
+    Total,
+    /// rank of the stars, from the personal stats.
+    Rank,
+    /// time from the unlock of the puzzle to the stars, from the personal stats.
+    SolveTime,
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(Column::Day),
+            "part_1" => Ok(Column::Part1),
+            "part_2" => Ok(Column::Part2),
+            "parse" => Ok(Column::Parse),
+            "samples" => Ok(Column::Samples),
+            "stddev" => Ok(Column::Stddev),
+            "heap" => Ok(Column::Heap),
+            "allocations" => Ok(Column::Allocations),
+            "cost" => Ok(Column::Cost),
+            "stars" => Ok(Column::Stars),
+            "total" => Ok(Column::Total),
+            "rank" => Ok(Column::Rank),
+            "solve_time" => Ok(Column::SolveTime),
+            _ => Err(format!("unknown column: {s}")),
+        }
+    }
+}
+
+impl Column {
+    fn header(self) -> &'static str {
+        match self {
+            Column::Day => "Day",
+            Column::Part1 => "Part 1",
+            Column::Part2 => "Part 2",
+            Column::Parse => "Parse",
+            Column::Samples => "Samples",
+            Column::Stddev => "Std. dev.",
+            Column::Heap => "Heap (peak)",
+            Column::Allocations => "Allocations",
+            Column::Cost => "Cost",
+            Column::Stars => "Stars",
+            Column::Total => "Total",
+            Column::Rank => "Rank",
+            Column::SolveTime => "Solve time",
+        }
+    }
+
+    fn cell(self, timing: &Timing, stats: Option<&DayStats>, link: Option<String>) -> String {
+        let code = |value: Option<String>| format!("`{}`", value.unwrap_or_else(|| "-".into()));
+        let per_part = |part_1: Option<String>, part_2: Option<String>| {
+            format!("{} / {}", code(part_1), code(part_2))
+        };
+        // allocations counted by DHAT or, without a heap summary, by `--count-allocs`.
+        let allocations = |heap: Option<HeapSummary>, allocs: Option<AllocCount>| {
+            heap.map(|x| x.allocations)
+                .or(allocs.map(|x| x.allocations))
+                .map(|x| x.to_string())
+        };
+
+        match self {
+            Column::Day => {
+                let day = format!("Day {}", timing.day.into_inner());
+                link.map_or_else(|| day.clone(), |link| format!("[{day}]({link})"))
+            }
+            Column::Part1 => code(timing.part_1.clone()),
+            Column::Part2 => code(timing.part_2.clone()),
+            Column::Parse => code(timing.parse.clone()),
+            Column::Samples => per_part(
+                timing.part_1_samples.map(|x| x.to_string()),
+                timing.part_2_samples.map(|x| x.to_string()),
+            ),
+            Column::Stddev => per_part(timing.part_1_stddev.clone(), timing.part_2_stddev.clone()),
+            Column::Heap => per_part(
+                timing.part_1_heap.map(|x| format_bytes(x.peak_bytes)),
+                timing.part_2_heap.map(|x| format_bytes(x.peak_bytes)),
+            ),
+            Column::Allocations => per_part(
+                allocations(timing.part_1_heap, timing.part_1_allocs),
+                allocations(timing.part_2_heap, timing.part_2_allocs),
+            ),
+            Column::Cost => per_part(
+                timing.part_1_cost.map(|x| x.to_string()),
+                timing.part_2_cost.map(|x| x.to_string()),
+            ),
+            Column::Stars => {
+                let stars =
+                    usize::from(timing.part_1.is_some()) + usize::from(timing.part_2.is_some());
+                "⭐".repeat(stars)
+            }
+            Column::Total => format!("`{:.2}ms`", timing.total_nanos / 1_000_000_f64),
+            Column::Rank => per_part(
+                stats
+                    .and_then(|s| s.part_1.as_ref())
+                    .and_then(|p| p.rank)
+                    .map(|x| x.to_string()),
+                stats
+                    .and_then(|s| s.part_2.as_ref())
+                    .and_then(|p| p.rank)
+                    .map(|x| x.to_string()),
+            ),
+            Column::SolveTime => per_part(
+                stats
+                    .and_then(|s| s.part_1.as_ref())
+                    .map(|p| p.time.clone()),
+                stats
+                    .and_then(|s| s.part_2.as_ref())
+                    .map(|p| p.time.clone()),
+            ),
+        }
+    }
+}
+
+/// Order of the rows in the benchmark table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortOrder {
+    Day,
+    DayDesc,
+    Time,
+    TimeDesc,
+}
+
+impl FromStr for SortOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(SortOrder::Day),
+            "day_desc" => Ok(SortOrder::DayDesc),
+            "time" => Ok(SortOrder::Time),
+            "time_desc" => Ok(SortOrder::TimeDesc),
+            _ => Err(format!("unknown sort order: {s}")),
+        }
+    }
+}
+
+impl SortOrder {
+    fn sort(self, data: &mut [Timing]) {
+        match self {
+            SortOrder::Day => data.sort_by_key(|t| t.day),
+            SortOrder::DayDesc => data.sort_by_key(|t| std::cmp::Reverse(t.day)),
+            SortOrder::Time => data.sort_by(|a, b| a.total_nanos.total_cmp(&b.total_nanos)),
+            SortOrder::TimeDesc => data.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos)),
+        }
+    }
+}
+
+/// Target of the link in the day column.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Link {
+    /// The solution file in `src/bin`.
+    Source,
+    /// The puzzle on adventofcode.com.
+    Puzzle,
+    None,
+    /// A custom URL with `%DAY_NUMBER%`, `%DAY_PADDED%` and `%YEAR%` placeholders.
+    Custom(String),
+}
+
+impl From<&str> for Link {
+    fn from(s: &str) -> Self {
+        match s {
+            "source" => Link::Source,
+            "puzzle" => Link::Puzzle,
+            "none" => Link::None,
+            _ => Link::Custom(s.into()),
+        }
+    }
+}
+
+impl Link {
+    fn url(&self, bin: &str, day: Day, year: Option<&str>) -> Option<String> {
+        let replace = |template: &str| {
+            let url = template
+                .replace("%DAY_NUMBER%", &day.into_inner().to_string())
+                .replace("%DAY_PADDED%", &day.to_string());
+
+            match year {
+                Some(year) => Some(url.replace("%YEAR%", year)),
+                None if url.contains("%YEAR%") => None,
+                None => Some(url),
+            }
+        };
+
+        match self {
+            Link::Source => Some(source_link(bin, day)),
+            Link::Puzzle => replace("https://adventofcode.com/%YEAR%/day/%DAY_NUMBER%"),
+            Link::None => None,
+            Link::Custom(template) => replace(template),
+        }
+    }
+}
+
+/// Configuration of a single benchmark table, set in the `[benchmarks]` table of the [`Config`].
+/// Further tables are added with a name, e.g. `[benchmarks.2023]`:
+///
+/// ```toml
+/// [benchmarks.2023]
+/// timings = "data/2023/timings.json"
+/// stats = "data/2023/stats.json"
+/// title = "Benchmarks 2023"
+/// heading_level = 3
+/// columns = "day, part_1, part_2, stddev, rank, solve_time"
+/// sort = "time_desc"
+/// link = "puzzle"
+/// year = 2023
+/// ```
+///
+/// All keys are optional. A table with a `name` is rendered between `<!--- benchmarking table <name> --->` markers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableConfig {
+    pub name: Option<String>,
+    /// timings file to render, defaults to the timings of the current run.
+    pub timings: Option<String>,
+    /// personal stats file for the `rank` and `solve_time` columns, defaults to the configured stats.
+    pub stats: Option<String>,
+    pub title: String,
+    pub heading_level: usize,
+    /// when not set, shows day and parts and adds a heap column if heap summaries were stored.
+    pub columns: Option<Vec<Column>>,
+    pub sort: SortOrder,
+    pub link: Link,
+    /// year used for puzzle links, defaults to the configured year.
+    pub year: Option<String>,
+}
+
+impl Default for TableConfig {
+    fn default() -> Self {
+        Self {
+            name: None,
+            timings: None,
+            stats: None,
+            title: "Benchmarks".into(),
+            heading_level: 2,
+            columns: None,
+            sort: SortOrder::Day,
+            link: Link::Source,
+            year: None,
+        }
+    }
+}
+
+impl TableConfig {
+    /// Set a single value, `key` is one of the keys of a table, e.g. `title`.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        let optional = |value: &str| (!value.is_empty()).then(|| value.to_string());
+
+        match key {
+            "timings" => self.timings = optional(value),
+            "stats" => self.stats = optional(value),
+            "title" => self.title = value.into(),
+            "heading_level" => {
+                self.heading_level = value
+                    .parse()
+                    .ok()
+                    .filter(|level| (1..=6).contains(level))
+                    .ok_or_else(|| {
+                        format!("expected `heading_level` to be a number between 1 and 6, got `{value}`.")
+                    })?;
+            }
+            "columns" => {
+                self.columns = if value.trim().is_empty() {
+                    None
+                } else {
+                    Some(
+                        value
+                            .split(',')
+                            .map(|column| column.trim().parse())
+                            .collect::<Result<_, _>>()?,
+                    )
+                };
+            }
+            "sort" => self.sort = value.parse()?,
+            "link" => self.link = Link::from(value),
+            "year" => self.year = optional(value),
+            _ => {
+                return Err(format!(
+                    "unknown key `{key}`, expected one of: timings, stats, title, heading_level, columns, sort, link, year."
+                ))
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The days of the event shown in the table: the configured days, unless the table is for another year.
+    fn days(&self, config: &Config) -> AllDays {
+        let year = self
+            .year
+            .as_deref()
+            .and_then(|year| year.parse::<u16>().ok());
+        match year {
+            Some(year) if config.year != Some(year) => AllDays::until(event_days(year.into())),
+            _ => config.all_days(),
+        }
+    }
+
+    fn marker(&self, default: &str) -> String {
+        self.name.as_ref().map_or_else(
+            || default.into(),
+            |name| format!("<!--- benchmarking table {name} --->"),
+        )
+    }
+
+    fn columns(&self, timings: &Timings) -> Vec<Column> {
+        if let Some(columns) = &self.columns {
+            return columns.clone();
+        }
+
+        // the heap column is only shown once heap summaries were stored via `cargo time --dhat`.
+        let has_heap = timings
+            .data
+            .iter()
+            .any(|t| t.part_1_heap.is_some() || t.part_2_heap.is_some());
+
+        let mut columns = vec![Column::Day, Column::Part1, Column::Part2];
+        if has_heap {
+            columns.push(Column::Heap);
+        }
+        columns
+    }
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/* -------------------------------------------------------------------------- */
+
+/// Locate the content between two occurences of `marker`, including the markers.
+pub fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+        return Err(Error::Parser(format!(
+            "{marker}: too many occurences of marker in README."
+        )));
     }
 
     let pos_start = matches
         .first()
         .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+        .ok_or_else(|| Error::Parser(format!("{marker}: could not find table start position.")))?;
 
     let pos_end = matches
         .last()
         .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
+        .ok_or_else(|| Error::Parser(format!("{marker}: could not find table end position.")))?;
 
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn construct_table(
+    config: &Config,
+    table: &TableConfig,
+    mut timings: Timings,
+    stats: &Stats,
+) -> String {
+    let marker = table.marker(&config.markers.benchmarks);
+    let header = format!("{} {}", "#".repeat(table.heading_level), table.title);
+    let total_millis = timings.total_millis();
+    let columns = table.columns(&timings);
+    let year = table
+        .year
+        .clone()
+        .or_else(|| config.year.map(|year| year.to_string()));
+
+    let row = |cells: Vec<String>| format!("| {} |", cells.join(" | "));
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let mut lines: Vec<String> = vec![marker.clone(), header, String::new()];
+    lines.push(row(columns.iter().map(|c| c.header().into()).collect()));
+    lines.push(row(columns.iter().map(|_| ":---:".into()).collect()));
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+    table.sort.sort(&mut timings.data);
+
+    for timing in &timings.data {
+        lines.push(row(columns
+            .iter()
+            .map(|column| {
+                let link = table
+                    .link
+                    .url(&config.paths.bin, timing.day, year.as_deref());
+                column.cell(timing, stats.get(timing.day), link)
+            })
+            .collect()));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker);
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
+fn update_content(
+    s: &mut String,
+    config: &Config,
+    tables: &[TableConfig],
+    timings: &Timings,
+    stats: &Stats,
+) -> Result<(), Error> {
+    for table in tables {
+        let mut timings = match &table.timings {
+            Some(path) => Timings::read_from_path(path).map_err(Error::Parser)?,
+            None => timings.clone(),
+        };
+        // stored timings of days outside the event are kept in the file, but not shown.
+        let days: Vec<Day> = table.days(config).collect();
+        timings.data.retain(|timing| days.contains(&timing.day));
+        let stats = match &table.stats {
+            Some(path) => Stats::read_from_path(path).map_err(Error::Parser)?,
+            None => stats.clone(),
+        };
+
+        let positions = locate_table(s, &table.marker(&config.markers.benchmarks))?;
+        let table = construct_table(config, table, timings, &stats);
+        s.replace_range(positions.range(), &table);
+    }
+
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
+pub fn update(config: &Config, timings: Timings) -> Result<(), Error> {
+    let path = &config.paths.readme;
+    let stats = config.stats_path(config.event_year(SystemClock.now()));
+    let stats = Stats::read_from_path(&stats).map_err(Error::Parser)?;
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, config, &config.tables, &timings, &stats)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{update_content, Column, Link, SortOrder, TableConfig};
+    use crate::template::alloc::AllocCount;
+    use crate::template::stats::Stats;
+    use crate::template::test_server::STATS;
+    use crate::template::Config;
+    use crate::{
+        day, template::timings::HeapSummary, template::timings::Timing, template::timings::Timings,
+    };
+
+    const MARKER: &str = "<!--- benchmarking table --->";
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -109,50 +512,93 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    total_nanos: 3e+7,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    part_1_cost: None,
+                    part_2_cost: None,
+                    parse: None,
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    part_1_stddev: None,
+                    part_2_stddev: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    total_nanos: 7e+7,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    part_1_cost: None,
+                    part_2_cost: None,
+                    parse: None,
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    part_1_stddev: None,
+                    part_2_stddev: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
+                    total_nanos: 9e+7,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    part_1_cost: None,
+                    part_2_cost: None,
+                    parse: None,
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    part_1_stddev: None,
+                    part_2_stddev: None,
                 },
             ],
         }
     }
 
+    fn update_default(s: &mut String, timings: Timings) -> Result<(), super::Error> {
+        update_content(
+            s,
+            &Config::default(),
+            &[TableConfig::default()],
+            &timings,
+            &Stats::default(),
+        )
+    }
+
     #[test]
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_default(&mut s, get_mock_timings()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_default(&mut s, get_mock_timings()).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_default(&mut s, get_mock_timings()).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_default(&mut s, get_mock_timings()).unwrap();
+        update_default(&mut s, get_mock_timings()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +606,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_default(&mut s, get_mock_timings()).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -168,7 +614,7 @@ mod tests {
             "## Benchmarks",
             "",
             "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
+            "| :---: | :---: | :---: |",
             "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
             "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
             "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
@@ -180,4 +626,180 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_heap() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_heap = Some(HeapSummary {
+            total_bytes: 4096,
+            peak_bytes: 1536,
+            allocations: 4,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_default(&mut s, timings).unwrap();
+
+        assert_eq!(s.contains("| Day | Part 1 | Part 2 | Heap (peak) |"), true);
+        assert_eq!(
+            s.contains("| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `1.50 KiB` / `-` |"),
+            true
+        );
+        assert_eq!(
+            s.contains("| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | `-` / `-` |"),
+            true
+        );
+    }
+
+    #[test]
+    fn format_allocation_counts() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_heap = Some(HeapSummary {
+            total_bytes: 4096,
+            peak_bytes: 1536,
+            allocations: 4,
+        });
+        timings.data[0].part_2_allocs = Some(AllocCount {
+            allocations: 2,
+            bytes: 64,
+        });
+
+        let config = TableConfig {
+            columns: Some(vec![Column::Day, Column::Allocations]),
+            link: Link::None,
+            ..TableConfig::default()
+        };
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            &Config::default(),
+            &[config],
+            &timings,
+            &Stats::default(),
+        )
+        .unwrap();
+
+        assert_eq!(s.contains("| Day 1 | `4` / `2` |"), true);
+        assert_eq!(s.contains("| Day 2 | `-` / `-` |"), true);
+    }
+
+    #[test]
+    fn format_configured_benchmarks() {
+        let mut timings = get_mock_timings();
+        timings.data[2].part_2 = None;
+        timings.data[2].part_1_samples = Some(243);
+        timings.data[2].part_1_stddev = Some("1.2ms".into());
+
+        let config = TableConfig {
+            title: "Timings".into(),
+            heading_level: 3,
+            columns: Some(vec![
+                Column::Day,
+                Column::Samples,
+                Column::Stddev,
+                Column::Stars,
+            ]),
+            sort: SortOrder::TimeDesc,
+            link: Link::Puzzle,
+            year: Some("2023".into()),
+            ..TableConfig::default()
+        };
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            &Config::default(),
+            &[config],
+            &timings,
+            &Stats::default(),
+        )
+        .unwrap();
+
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(lines[1], "### Timings");
+        assert_eq!(lines[3], "| Day | Samples | Std. dev. | Stars |");
+        assert_eq!(
+            lines[5],
+            "| [Day 4](https://adventofcode.com/2023/day/4) | `243` / `-` | `1.2ms` / `-` | ⭐ |"
+        );
+        assert_eq!(lines[7].starts_with("| [Day 1]"), true);
+    }
+
+    #[test]
+    fn format_benchmarks_with_stats() {
+        let config = TableConfig {
+            columns: Some(vec![Column::Day, Column::Rank, Column::SolveTime]),
+            link: Link::None,
+            ..TableConfig::default()
+        };
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            &Config::default(),
+            &[config],
+            &get_mock_timings(),
+            &Stats::parse_page(STATS).unwrap(),
+        )
+        .unwrap();
+
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(lines[3], "| Day | Rank | Solve time |");
+        assert_eq!(
+            lines[5],
+            "| Day 1 | `98` / `1234` | `00:01:01` / `00:05:00` |"
+        );
+        assert_eq!(lines[7], "| Day 4 | `-` / `-` | `-` / `-` |");
+    }
+
+    #[test]
+    fn updates_multiple_tables() {
+        let named = TableConfig {
+            name: Some("2023".into()),
+            link: Link::None,
+            ..TableConfig::default()
+        };
+
+        let named_marker = "<!--- benchmarking table 2023 --->";
+        let mut s = format!("{MARKER}{MARKER}\n{named_marker}{named_marker}");
+        update_content(
+            &mut s,
+            &Config::default(),
+            &[TableConfig::default(), named],
+            &get_mock_timings(),
+            &Stats::default(),
+        )
+        .unwrap();
+
+        assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
+        assert_eq!(s.matches(named_marker).collect::<Vec<&str>>().len(), 2);
+        assert_eq!(s.contains("| [Day 1](./src/bin/01.rs) |"), true);
+        assert_eq!(s.contains("| Day 1 |"), true);
+    }
+
+    #[test]
+    fn parses_table_config() {
+        let mut config = TableConfig::default();
+        config.set("heading_level", "3").unwrap();
+        config.set("columns", "day, parse,cost").unwrap();
+        config.set("link", "./solutions/%DAY_PADDED%.rs").unwrap();
+
+        assert_eq!(config.heading_level, 3);
+        assert_eq!(
+            config.columns,
+            Some(vec![Column::Day, Column::Parse, Column::Cost])
+        );
+        assert_eq!(
+            config.link.url("src/bin", day!(4), None).unwrap(),
+            "./solutions/04.rs"
+        );
+        assert_eq!(config.sort, SortOrder::Day);
+
+        assert_eq!(config.set("columns", "day, speed").is_err(), true);
+        assert_eq!(config.set("heading_level", "7").is_err(), true);
+        assert_eq!(config.set("colour", "red").is_err(), true);
+
+        config.set("columns", "").unwrap();
+        assert_eq!(config.columns, None);
+    }
 }
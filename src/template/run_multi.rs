@@ -1,19 +1,25 @@
 use std::{collections::HashSet, io};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::visualize::ExportFormat;
+use crate::template::{Config, Day, InputSource, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
-    all_days,
     timings::{Timing, Timings},
+    AllDays,
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+pub fn run_multi(
+    config: &Config,
+    days_to_run: &HashSet<Day>,
+    options: RunOptions,
+    is_timed: bool,
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    AllDays::until(Day::LIMIT)
         .filter(|day| days_to_run.contains(day))
         .for_each(|day| {
             if need_space {
@@ -24,7 +30,9 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let output =
+                child_commands::run_solution(config, day, is_timed, options, &InputSource::Puzzle)
+                    .unwrap();
 
             if output.is_empty() {
                 println!("Not solved.");
@@ -46,6 +54,86 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     }
 }
 
+/// Controls how a solution binary is built and which measurements it takes.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RunOptions {
+    pub release: bool,
+    /// profile heap usage with DHAT.
+    pub dhat: bool,
+    /// count allocations with a counting global allocator.
+    pub count_allocs: bool,
+    /// sample CPU usage and write flamegraphs.
+    pub profile_cpu: bool,
+    /// measure a hardware-independent cost metric.
+    pub cost: bool,
+    /// render grid simulations in the terminal, see [`crate::template::visualize`].
+    pub visualize: bool,
+    /// write the frames of grid simulations to image files.
+    pub export: Option<ExportFormat>,
+    /// print the output of `debug!` in solutions.
+    pub verbose: bool,
+}
+
+impl RunOptions {
+    /// Arguments for `cargo run` that select the build profile and cargo features.
+    pub fn cargo_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if self.dhat {
+            args.extend(["--profile".into(), "dhat".into()]);
+        } else if self.profile_cpu {
+            args.extend(["--profile".into(), "profiling".into()]);
+        } else if self.release {
+            args.push("--release".into());
+        }
+
+        let features: Vec<&str> = [
+            (self.dhat, "dhat-heap"),
+            (self.count_allocs, "count-allocs"),
+            (self.profile_cpu, "cpu-profile"),
+            (self.cost, "cost-counters"),
+            (self.export.is_some(), "visualize-export"),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, feature)| enabled.then_some(feature))
+        .collect();
+
+        if !features.is_empty() {
+            args.extend(["--features".into(), features.join(",")]);
+        }
+
+        args
+    }
+
+    /// Arguments that are passed on to the solution binary.
+    pub fn binary_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if self.profile_cpu {
+            args.push("--profile-cpu".into());
+        }
+
+        if self.cost {
+            args.push("--cost".into());
+        }
+
+        if self.visualize {
+            args.push("--visualize".into());
+        }
+
+        if self.verbose {
+            args.push("--verbose".into());
+        }
+
+        if let Some(format) = self.export {
+            args.extend(["--export".into(), format.to_string()]);
+        }
+
+        args
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -59,16 +147,13 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{Error, RunOptions};
+    use crate::template::alloc::AllocCount;
+    use crate::template::timings::{parse_duration, Cost, HeapSummary};
+    use crate::template::{Config, Day, InputSource};
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -77,30 +162,46 @@ pub mod child_commands {
     };
 
     /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    pub fn run_solution(
+        config: &Config,
+        day: Day,
+        is_timed: bool,
+        options: RunOptions,
+        input: &InputSource,
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
+        if !Path::new(&config.bin_path(day)).exists() {
             return Ok(vec![]);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
-
-        if is_release {
-            args.push("--release");
-        }
+        let mut args: Vec<String> = vec![
+            "run".into(),
+            "--quiet".into(),
+            "--bin".into(),
+            day.to_string(),
+        ];
+        args.extend(options.cargo_args());
+        args.push("--".into());
 
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+            args.push("--time".into());
+        }
+
+        if options.dhat {
+            // only collect heap summaries, skip writing a DHAT report per part.
+            args.push("--dhat-summary".into());
         }
 
+        args.extend(options.binary_args());
+        args.extend(input.to_args());
+
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
         let mut cmd = Command::new("cargo")
             .args(&args)
+            .envs(config.env_vars())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -134,6 +235,17 @@ pub mod child_commands {
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            part_1_heap: None,
+            part_2_heap: None,
+            part_1_allocs: None,
+            part_2_allocs: None,
+            part_1_cost: None,
+            part_2_cost: None,
+            parse: None,
+            part_1_samples: None,
+            part_2_samples: None,
+            part_1_stddev: None,
+            part_2_stddev: None,
         };
 
         output
@@ -149,13 +261,26 @@ pub mod child_commands {
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                Some((part, l, timing_str, nanos))
             })
-            .for_each(|(part, timing_str, nanos)| {
+            .for_each(|(part, line, timing_str, nanos)| {
                 if part.contains("Part 1") {
                     timings.part_1 = Some(timing_str.into());
+                    timings.part_1_heap = parse_heap(line);
+                    timings.part_1_allocs = parse_allocs(line);
+                    timings.part_1_cost = parse_cost(line);
+                    timings.part_1_samples = parse_samples(line);
+                    timings.part_1_stddev = parse_stddev(line);
                 } else if part.contains("Part 2") {
                     timings.part_2 = Some(timing_str.into());
+                    timings.part_2_heap = parse_heap(line);
+                    timings.part_2_allocs = parse_allocs(line);
+                    timings.part_2_cost = parse_cost(line);
+                    timings.part_2_samples = parse_samples(line);
+                    timings.part_2_stddev = parse_stddev(line);
+                } else if part.contains("Parse") {
+                    // the parts receive the parsed input, parsing counts towards the total.
+                    timings.parse = Some(timing_str.into());
                 }
 
                 timings.total_nanos += nanos;
@@ -164,12 +289,105 @@ pub mod child_commands {
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    /// Extract the printed answer of each part from a solution's output.
+    /// Multi-line answers are printed on the lines following the `▼` marker.
+    pub fn parse_answers(output: &[String]) -> [Option<String>; 2] {
+        let mut answers = [None, None];
+        let mut multiline: Option<(usize, Vec<&str>)> = None;
+
+        for line in output {
+            // intermediate results are overwritten with a carriage return, keep the final one.
+            let line = line.rsplit('\r').next().unwrap_or(line);
+
+            let line_part = ["Part 1: ", "Part 2: "]
+                .iter()
+                .position(|prefix| line.starts_with(prefix));
+
+            let Some(index) = line_part else {
+                if let Some((_, lines)) = multiline.as_mut() {
+                    lines.push(line);
+                }
+                continue;
+            };
+
+            if let Some((index, lines)) = multiline.take() {
+                answers[index] = Some(lines.join("\n").trim_end().to_string());
+            }
+
+            let rest = strip_ansi(&line["Part 1: ".len()..]);
+
+            if rest.starts_with('✖') {
+                answers[index] = None;
+            } else if rest.starts_with('▼') {
+                multiline = Some((index, vec![]));
+            } else {
+                let answer = rest.rsplit_once(" (").map_or(rest.as_str(), |(a, _)| a);
+                answers[index] = Some(answer.trim().to_string());
+            }
+        }
+
+        if let Some((index, lines)) = multiline {
+            answers[index] = Some(lines.join("\n").trim_end().to_string());
+        }
+
+        answers
+    }
+
+    fn strip_ansi(s: &str) -> String {
+        let mut result = String::with_capacity(s.len());
+        let mut chars = s.chars();
+
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                // skip the escape sequence up to and including its final byte.
+                for c in chars.by_ref() {
+                    if c.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            } else {
+                result.push(c);
+            }
+        }
+
+        result
+    }
+
+    fn parse_heap(line: &str) -> Option<HeapSummary> {
+        let (_, rest) = line.split_once("[heap: ")?;
+        let (summary, _) = rest.split_once(']')?;
+        summary.parse().ok()
+    }
+
+    fn parse_allocs(line: &str) -> Option<AllocCount> {
+        let (_, rest) = line.split_once("[allocs: ")?;
+        let (count, _) = rest.split_once(" per run]")?;
+        count.parse().ok()
+    }
+
+    fn parse_samples(line: &str) -> Option<u64> {
+        line.split(" samples)")
+            .next()?
+            .rsplit('@')
+            .next()?
+            .trim()
+            .parse()
+            .ok()
+    }
+
+    fn parse_stddev(line: &str) -> Option<String> {
+        let (_, rest) = line.split_once("[stddev: ")?;
+        let (stddev, _) = rest.split_once(']')?;
+        Some(stddev.into())
+    }
+
+    fn parse_cost(line: &str) -> Option<Cost> {
+        let (_, rest) = line.split_once("[cost: ")?;
+        let (cost, _) = rest.split_once(']')?;
+        cost.parse().ok()
     }
 
     fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
         let str_timing = line
             .split(" samples)")
             .next()?
@@ -179,14 +397,7 @@ pub mod child_commands {
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+        Some((str_timing, parse_duration(str_timing)?))
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -205,7 +416,7 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_answers, parse_exec_time};
 
         use crate::day;
 
@@ -239,6 +450,75 @@ pub mod child_commands {
             assert_eq!(res.part_2.unwrap(), "100ms");
         }
 
+        #[test]
+        fn parses_heap_summaries() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples) [heap: 2048 B total, 1024 B peak, 3 allocs]"
+                        .into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            let heap = res.part_1_heap.unwrap();
+            assert_eq!(heap.total_bytes, 2048);
+            assert_eq!(heap.peak_bytes, 1024);
+            assert_eq!(heap.allocations, 3);
+            assert_eq!(res.part_2_heap, None);
+        }
+
+        #[test]
+        fn parses_alloc_counts() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples) [allocs: 3 allocs, 276 B per run]".into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            let allocs = res.part_1_allocs.unwrap();
+            assert_eq!(allocs.allocations, 3);
+            assert_eq!(allocs.bytes, 276);
+            assert_eq!(res.part_2_allocs, None);
+        }
+
+        #[test]
+        fn parses_benchmark_details() {
+            let res = parse_exec_time(
+                &[
+                    "Parse: (1.2ms @ 812 samples) [stddev: 80.0µs]".into(),
+                    "Part 1: 0 (74.13ns @ 100000 samples) [stddev: 3.2ns]".into(),
+                    "Part 2: 10 (74.13ms)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 1_200_074.13_f64);
+            assert_eq!(res.parse.unwrap(), "1.2ms");
+            assert_eq!(res.part_1_samples, Some(100000));
+            assert_eq!(res.part_1_stddev.unwrap(), "3.2ns");
+            assert_eq!(res.part_2, None);
+            assert_eq!(res.part_2_samples, None);
+        }
+
+        #[test]
+        fn parses_costs() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples) [cost: 1234 instructions]".into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples) [heap: 2048 B total, 1024 B peak, 3 allocs] [cost: 56 iterations]"
+                        .into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_1_cost.unwrap().to_string(), "1234 instructions");
+            assert_eq!(res.part_2_cost.unwrap().to_string(), "56 iterations");
+            assert!(res.part_2_heap.is_some());
+        }
+
         #[test]
         fn parses_missing_parts() {
             let res = parse_exec_time(
@@ -253,5 +533,28 @@ pub mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn parses_answers() {
+            let res = parse_answers(&[
+                "Part 1: \x1b[1m42\x1b[0m > benching\rPart 1: \x1b[1m42\x1b[0m (74.13ns @ 100000 samples)".into(),
+                "Part 2: ✖             ".into(),
+                "".into(),
+            ]);
+            assert_eq!(res[0].as_deref(), Some("42"));
+            assert_eq!(res[1], None);
+        }
+
+        #[test]
+        fn parses_multiline_answers() {
+            let res = parse_answers(&[
+                "Part 1: 1 (1.0ms)".into(),
+                "Part 2: ▼ \rPart 2: ▼  (2.0ms)".into(),
+                "#..#".into(),
+                ".##.".into(),
+            ]);
+            assert_eq!(res[0].as_deref(), Some("1"));
+            assert_eq!(res[1].as_deref(), Some("#..#\n.##."));
+        }
     }
 }
@@ -2,80 +2,287 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::template::alloc::AllocCount;
+#[cfg(feature = "cpu-profile")]
+use crate::template::cpu_profile;
+use crate::template::puzzle::Puzzle;
+use crate::template::submission;
+use crate::template::timings::{Cost, HeapSummary};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    aoc_cli, cost, visualize, Config, Day, InputSource, ANSI_ITALIC, ANSI_RESET,
+};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+const BENCH_DURATION: Duration = Duration::from_secs(1);
+const MIN_BENCH_ITERATIONS: u128 = 10;
+
+pub fn run_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: u8,
+    config: &Config,
+) {
     let part_str = format!("Part {part}");
+    visualize::set_part(day, part, config);
+
+    let (result, measurement) = run_timed(func, input, day, part, config, |result| {
+        print_result(result, &part_str, "");
+    });
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    print_result(&result, &part_str, &format_measurement(&measurement));
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    if let Some(profile) = measurement.profile {
+        println!("  {ANSI_ITALIC}CPU profile written to {profile}{ANSI_RESET}");
+    }
 
     if let Some(result) = result {
-        submit_result(result, day, part);
+        submit_result(config, result, day, part);
     }
 }
 
+/// Run and time the separate parse step of a solution, returns the parsed input for the parts.
+pub fn run_parse<I: Clone, T>(func: impl Fn(I) -> T, input: I) -> T {
+    print!("Parse:");
+    let _ = stdout().flush();
+
+    let timer = Instant::now();
+    let parsed = black_box(func(input.clone()));
+    let base_time = timer.elapsed();
+
+    let measurement = if std::env::args().any(|x| x == "--time") {
+        bench(func, input, &base_time)
+    } else {
+        Measurement {
+            duration: base_time,
+            samples: 1,
+            stddev: None,
+            heap: None,
+            allocs: None,
+            profile: None,
+            cost: None,
+        }
+    };
+
+    print!("\r");
+    println!("Parse:{}", format_measurement(&measurement));
+
+    parsed
+}
+
+/// Measurements taken while running a solution part.
+struct Measurement {
+    duration: Duration,
+    samples: u128,
+    /// standard deviation of the samples, only present if the part was benched.
+    stddev: Option<Duration>,
+    heap: Option<HeapSummary>,
+    /// mean allocations per run, only present if the `count-allocs` feature is active.
+    allocs: Option<AllocCount>,
+    /// path of the flamegraph, only present if the `cpu-profile` feature is active.
+    profile: Option<String>,
+    /// cost of the first run, only present if the `cost-counters` feature is active and `--cost` is passed.
+    cost: Option<Cost>,
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// When the `dhat-heap` feature is active, the first execution is profiled and a heap summary is returned.
+/// When the `cpu-profile` feature is active and `--profile-cpu` is passed, the function is additionally
+/// run under a sampling profiler for the duration of a benchmark.
+/// When the `cost-counters` feature is active and `--cost` is passed, the cost of the first execution is measured.
+#[cfg_attr(not(feature = "cpu-profile"), allow(unused_variables))]
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    day: Day,
+    part: u8,
+    config: &Config,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Measurement) {
     let timer = Instant::now();
-    let result = {
+    let (result, heap, allocs, cost) = {
         let input = input.clone();
 
         #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
+        let _profiler = dhat_profiler(day, part);
+
+        let ((result, cost), allocs) = with_alloc_count(|| with_cost(|| func(input)));
 
-        func(input)
+        #[cfg(feature = "dhat-heap")]
+        let heap = Some(heap_summary());
+        #[cfg(not(feature = "dhat-heap"))]
+        let heap = None;
+
+        (result, heap, allocs, cost)
     };
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    #[cfg(feature = "cpu-profile")]
+    let profile = if std::env::args().any(|x| x == "--profile-cpu") {
+        profile_cpu(&func, input.clone(), day, part, &config.paths.profiles)
+    } else {
+        None
+    };
+    #[cfg(not(feature = "cpu-profile"))]
+    let profile = None;
+
+    let measurement = if std::env::args().any(|x| x == "--time") {
+        let mut measurement = bench(func, input, &base_time);
+        measurement.heap = heap;
+        measurement.profile = profile;
+        measurement.cost = cost;
+        measurement
     } else {
-        (base_time, 1)
+        Measurement {
+            duration: base_time,
+            samples: 1,
+            stddev: None,
+            heap,
+            allocs,
+            profile,
+            cost,
+        }
     };
 
-    (result, run.0, run.1)
+    (result, measurement)
+}
+
+/// Start a heap profiler for a single part.
+/// With `--dhat-summary`, only the summary is collected and no JSON report is written.
+#[cfg(feature = "dhat-heap")]
+fn dhat_profiler(day: Day, part: u8) -> dhat::Profiler {
+    if env::args().any(|x| x == "--dhat-summary") {
+        // testing mode disables writing the report when the profiler is dropped.
+        dhat::Profiler::builder().testing().build()
+    } else {
+        dhat::Profiler::builder()
+            .file_name(format!("dhat-heap-{day}-{part}.json"))
+            .build()
+    }
+}
+
+#[cfg(feature = "dhat-heap")]
+fn heap_summary() -> HeapSummary {
+    let stats = dhat::HeapStats::get();
+    HeapSummary {
+        total_bytes: stats.total_bytes,
+        peak_bytes: stats.max_bytes as u64,
+        allocations: stats.total_blocks,
+    }
+}
+
+#[cfg(feature = "cpu-profile")]
+fn profile_cpu<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    day: Day,
+    part: u8,
+    dir: &str,
+) -> Option<String> {
+    print!(" > {ANSI_ITALIC}profiling{ANSI_RESET}");
+    let _ = stdout().flush();
+
+    match cpu_profile::profile(
+        func,
+        input,
+        BENCH_DURATION,
+        MIN_BENCH_ITERATIONS,
+        day,
+        part,
+        dir,
+    ) {
+        Ok(output) => Some(format!(
+            "{} ({})",
+            output.flamegraph.display(),
+            output.folded.display()
+        )),
+        Err(e) => {
+            eprintln!("\nFailed to profile part {part}: {e}");
+            None
+        }
+    }
+}
+
+/// Run `f`, counting its allocations if the counting allocator is installed.
+#[cfg(all(feature = "count-allocs", not(feature = "dhat-heap")))]
+fn with_alloc_count<T>(f: impl FnOnce() -> T) -> (T, Option<AllocCount>) {
+    let before = AllocCount::now();
+    let result = f();
+    (result, Some(AllocCount::now().since(before)))
+}
+
+#[cfg(not(all(feature = "count-allocs", not(feature = "dhat-heap"))))]
+fn with_alloc_count<T>(f: impl FnOnce() -> T) -> (T, Option<AllocCount>) {
+    (f(), None)
+}
+
+/// Run `f`, measuring its cost if `--cost` is passed.
+fn with_cost<T>(f: impl FnOnce() -> T) -> (T, Option<Cost>) {
+    if env::args().any(|x| x == "--cost") {
+        cost::measure(f)
+    } else {
+        (f(), None)
+    }
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> Measurement {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let bench_iterations = bench_iterations(base_time);
 
     let mut timers: Vec<Duration> = vec![];
+    let mut allocs: Option<AllocCount> = None;
 
     for _ in 0..bench_iterations {
         // need a clone here to make the borrow checker happy.
         let cloned = input.clone();
-        let timer = Instant::now();
-        black_box(func(black_box(cloned)));
-        timers.push(timer.elapsed());
+        let (elapsed, count) = with_alloc_count(|| {
+            let timer = Instant::now();
+            black_box(func(black_box(cloned)));
+            timer.elapsed()
+        });
+        timers.push(elapsed);
+
+        if let Some(count) = count {
+            let total = allocs.get_or_insert_with(AllocCount::default);
+            total.allocations += count.allocations;
+            total.bytes += count.bytes;
+        }
     }
 
-    (
+    #[allow(clippy::cast_possible_truncation)]
+    let iterations = bench_iterations as u64;
+
+    Measurement {
         #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+        duration: Duration::from_nanos(average_duration(&timers) as u64),
+        samples: bench_iterations,
+        stddev: Some(standard_deviation(&timers)),
+        heap: None,
+        profile: None,
+        cost: None,
+        allocs: allocs.map(|total| AllocCount {
+            allocations: total.allocations / iterations,
+            bytes: total.bytes / iterations,
+        }),
+    }
+}
+
+/// Number of runs for a benchmark: approx. 1 second of execution time or 10 samples, whatever takes longer.
+fn bench_iterations(base_time: &Duration) -> u128 {
+    (BENCH_DURATION.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+        .clamp(MIN_BENCH_ITERATIONS, 10000)
 }
 
 fn average_duration(numbers: &[Duration]) -> u128 {
@@ -86,6 +293,21 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn standard_deviation(numbers: &[Duration]) -> Duration {
+    let mean = average_duration(numbers) as f64;
+    let variance = numbers
+        .iter()
+        .map(|x| (x.as_nanos() as f64 - mean).powi(2))
+        .sum::<f64>()
+        / numbers.len() as f64;
+    Duration::from_nanos(variance.sqrt() as u64)
+}
+
 fn format_duration(duration: &Duration, samples: u128) -> String {
     if samples == 1 {
         format!(" ({duration:.1?})")
@@ -94,6 +316,28 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
+fn format_measurement(measurement: &Measurement) -> String {
+    let mut s = format_duration(&measurement.duration, measurement.samples);
+
+    if let Some(stddev) = measurement.stddev {
+        s.push_str(&format!(" [stddev: {stddev:.1?}]"));
+    }
+
+    if let Some(heap) = measurement.heap {
+        s.push_str(&format!(" [heap: {heap}]"));
+    }
+
+    if let Some(allocs) = measurement.allocs {
+        s.push_str(&format!(" [allocs: {allocs} per run]"));
+    }
+
+    if let Some(cost) = measurement.cost {
+        s.push_str(&format!(" [cost: {cost}]"));
+    }
+
+    s
+}
+
 fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
@@ -132,11 +376,14 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
+///  3. the solution ran against the puzzle input.
+///  4. the answer is a single line, and the submission is confirmed or `--yes` is passed.
 fn submit_result<T: Display>(
+    config: &Config,
     result: T,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<String, aoc_cli::AocCommandError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -159,11 +406,64 @@ fn submit_result<T: Display>(
         return None;
     }
 
+    if InputSource::from_args(&args) != InputSource::Puzzle {
+        eprintln!("Refusing to submit a result that was not computed from the puzzle input.");
+        process::exit(1);
+    }
+
+    // debug builds are not what is benchmarked, and overflows may behave differently.
+    if cfg!(debug_assertions) {
+        eprintln!("Refusing to submit a result from a debug build, run with --release.");
+        process::exit(1);
+    }
+
+    let answer = result.to_string();
+    if let Err(e) = submission::validate(&answer) {
+        eprintln!("Refusing to submit: {e}");
+        process::exit(1);
+    }
+
+    let example_answer = Puzzle::read(config, day).and_then(|puzzle| {
+        let [part_one, part_two] = puzzle.answers;
+        if part == 1 {
+            part_one
+        } else {
+            part_two
+        }
+    });
+    for warning in submission::warnings(&answer, example_answer.as_deref()) {
+        println!("⚠️  Warning: {warning}");
+    }
+
+    if !args.contains(&"--yes".into()) {
+        match submission::confirm(&format!(
+            "Submit {ANSI_BOLD}{answer}{ANSI_RESET} for part {part} of day {day}?"
+        )) {
+            Ok(true) => {}
+            Ok(false) => {
+                println!("Not submitted.");
+                return None;
+            }
+            Err(e) => {
+                eprintln!("Refusing to submit: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let output = aoc_cli::submit(config, day, part, &answer);
+
+    match &output {
+        // the response of the server is echoed by `submit` and logged.
+        Ok(_) | Err(aoc_cli::AocCommandError::BadExitStatus(_)) => {}
+        Err(e) => eprintln!("Failed to submit: {e}"),
+    }
+
+    Some(output)
 }
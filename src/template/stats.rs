@@ -0,0 +1,266 @@
+/// Personal stats: the rank and the time since unlock at which each star of an event was earned.
+/// Imported from the personal stats page at `/<year>/leaderboard/self`, or a saved copy of it, and stored as JSON.
+use std::{collections::HashMap, fs, io::Error, path::Path, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{aoc_cli, read_if_exists, Config, Day};
+
+/// The time and rank of a single star.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartStats {
+    /// time from the unlock of the puzzle to the star as shown on the stats page, e.g. `00:12:34` or `>24h`.
+    pub time: String,
+    /// not shown for events without a global leaderboard.
+    pub rank: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayStats {
+    pub day: Day,
+    pub part_1: Option<PartStats>,
+    pub part_2: Option<PartStats>,
+}
+
+/// Personal stats for the days of an event.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub data: Vec<DayStats>,
+}
+
+impl Stats {
+    /// Dehydrate stats to a JSON file at `path`.
+    pub fn store_file(&self, path: &str) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        if let Some(dir) = Path::new(path).parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate stats from a JSON file at `path`. If not present, returns empty stats.
+    /// Errors if the file can not be read or parsed.
+    pub fn read_from_path(path: &str) -> Result<Self, String> {
+        match read_if_exists(path)? {
+            Some(s) => Stats::try_from(s.as_str()).map_err(|e| format!("{path}: {e}")),
+            None => Ok(Stats::default()),
+        }
+    }
+
+    pub fn get(&self, day: Day) -> Option<&DayStats> {
+        self.data.iter().find(|stats| stats.day == day)
+    }
+
+    /// Parse the personal stats page, either as html or as the text copied from a browser.
+    pub fn parse_page(page: &str) -> Result<Self, String> {
+        let text = strip_tags(page).replace("&gt;", ">").replace("&lt;", "<");
+
+        let mut data: Vec<DayStats> = text
+            .lines()
+            .filter_map(|line| {
+                let mut columns = line.split_whitespace();
+                let day = columns.next()?.parse().ok().and_then(Day::new)?;
+                let columns: Vec<&str> = columns.collect();
+
+                // `Time Rank Score` per part, or only `Time` for events without a global leaderboard.
+                let width = match columns.len() {
+                    6 => 3,
+                    2 => 1,
+                    _ => return None,
+                };
+
+                let part = |columns: &[&str]| {
+                    (columns[0] != "-").then(|| PartStats {
+                        time: columns[0].into(),
+                        rank: columns.get(1).and_then(|rank| rank.parse().ok()),
+                    })
+                };
+
+                Some(DayStats {
+                    day,
+                    part_1: part(&columns[..width]),
+                    part_2: part(&columns[width..]),
+                })
+            })
+            .collect();
+
+        if data.is_empty() {
+            return Err(
+                "could not find any stats, is the session cookie valid and a star earned?".into(),
+            );
+        }
+
+        data.sort_unstable_by_key(|stats| stats.day);
+        Ok(Stats { data })
+    }
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text
+}
+
+/// Download the personal stats page of the event `year`.
+pub fn download(config: &Config, year: i64) -> Result<String, String> {
+    aoc_cli::fetch_authenticated(config, &format!("/{year}/leaderboard/self"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Stats> for JsonValue {
+    fn from(value: &Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&str> for Stats {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .and_then(|json| json.get("data"))
+            .and_then(|data| data.get::<Vec<JsonValue>>())
+            .ok_or("expected JSON document to have a `data` array.")?;
+
+        Ok(Stats {
+            data: json_data
+                .iter()
+                .map(DayStats::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&DayStats> for JsonValue {
+    fn from(value: &DayStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        #[allow(clippy::cast_precision_loss)]
+        for (part, stats) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            if let Some(stats) = stats {
+                map.insert(
+                    format!("{part}_time"),
+                    JsonValue::String(stats.time.clone()),
+                );
+                if let Some(rank) = stats.rank {
+                    map.insert(format!("{part}_rank"), JsonValue::Number(rank as f64));
+                }
+            }
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for DayStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected stats to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("expected stats.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = |part: &str| {
+            json.get(&format!("{part}_time"))
+                .and_then(|v| v.get::<String>())
+                .map(|time| PartStats {
+                    time: time.clone(),
+                    rank: json
+                        .get(&format!("{part}_rank"))
+                        .and_then(|v| v.get::<f64>())
+                        .map(|rank| *rank as u64),
+                })
+        };
+
+        Ok(DayStats {
+            day,
+            part_1: part("part_1"),
+            part_2: part("part_2"),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{download, PartStats, Stats};
+    use crate::day;
+    use crate::template::test_server::{TestServer, STATS};
+    use crate::template::Config;
+
+    #[test]
+    fn parses_stats_page() {
+        let stats = Stats::parse_page(STATS).unwrap();
+
+        assert_eq!(stats.data.len(), 3);
+        assert_eq!(stats.data[0].day, day!(1));
+        assert_eq!(
+            stats.data[0].part_2,
+            Some(PartStats {
+                time: "00:05:00".into(),
+                rank: Some(1234)
+            })
+        );
+        assert_eq!(
+            stats.get(day!(3)).unwrap().part_1.as_ref().unwrap().time,
+            ">24h"
+        );
+        assert_eq!(stats.get(day!(3)).unwrap().part_2, None);
+
+        let text = "Day   Part 1   Part 2\n  1   00:01:01   00:05:00\n  2   01:01:01   -";
+        let stats = Stats::parse_page(text).unwrap();
+        assert_eq!(stats.data[1].part_1.as_ref().unwrap().rank, None);
+        assert_eq!(stats.data[1].part_2, None);
+
+        assert!(Stats::parse_page("<html>log in</html>").is_err());
+    }
+
+    #[test]
+    fn round_trips_stats() {
+        let stats = Stats::parse_page(STATS).unwrap();
+        let json = tinyjson::JsonValue::from(&stats).stringify().unwrap();
+        assert_eq!(Stats::try_from(json.as_str()).unwrap(), stats);
+    }
+
+    #[test]
+    fn downloads_stats_page() {
+        let server = TestServer::start();
+        let mut config = Config::default();
+        config.server.url.clone_from(&server.url);
+
+        assert_eq!(download(&config, 2024).unwrap(), STATS);
+        assert_eq!(server.requests(), vec!["GET /2024/leaderboard/self"]);
+    }
+}
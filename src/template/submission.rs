@@ -0,0 +1,328 @@
+/// Checks run before an answer is submitted, to avoid wasting an attempt and the cooldown that follows a wrong answer,
+/// and the log of submitted answers with the responses of the server.
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, BufRead, IsTerminal, Write},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+/// A submitted answer and the response of the server, one line of the log at `paths.submissions`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub year: Option<u16>,
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    /// unix timestamp of the submission.
+    pub timestamp: u64,
+    pub response: String,
+}
+
+impl Submission {
+    /// Unix timestamp until which the server does not accept another answer for the day.
+    pub fn cooldown_until(&self) -> u64 {
+        self.timestamp + parse_cooldown(&self.response)
+    }
+}
+
+/// Read the log at `path`. A missing log is empty, invalid lines are skipped with a warning.
+pub fn read_log(path: &str) -> Vec<Submission> {
+    let Ok(s) = fs::read_to_string(path) else {
+        return vec![];
+    };
+
+    s.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .filter_map(|(index, line)| match Submission::try_from(line) {
+            Ok(submission) => Some(submission),
+            Err(e) => {
+                eprintln!("{path}:{}: {e}", index + 1);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Append a submission to the log at `path`, one JSON object per line.
+pub fn append_to_log(path: &str, submission: &Submission) -> Result<(), String> {
+    let line = JsonValue::from(submission)
+        .stringify()
+        .map_err(|e| e.to_string())?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("could not open {path}: {e}"))?;
+
+    writeln!(file, "{line}").map_err(|e| format!("could not write {path}: {e}"))
+}
+
+/// Seconds left at `now` before an answer for `day` of `year` can be submitted, if a cooldown is running.
+pub fn remaining_cooldown(
+    log: &[Submission],
+    year: Option<u16>,
+    day: Day,
+    now: u64,
+) -> Option<u64> {
+    log.iter()
+        .filter(|submission| submission.year == year && submission.day == day)
+        .map(Submission::cooldown_until)
+        .max()
+        .filter(|until| *until > now)
+        .map(|until| until - now)
+}
+
+/// The cooldown in seconds the server asks for in a response, e.g. after a wrong answer
+/// `Please wait one minute before trying again.` or after a submission during the cooldown `You have 1m 54s left to wait.`
+/// Whitespace is normalized first, as aoc-cli wraps long responses over several lines.
+pub fn parse_cooldown(response: &str) -> u64 {
+    let response = response
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+
+    if let Some((_, rest)) = response.split_once("you have ") {
+        if let Some((left, _)) = rest.split_once(" left to wait") {
+            return left
+                .split_whitespace()
+                .map(|amount| {
+                    let (value, unit) = amount.split_at(amount.len().saturating_sub(1));
+                    let value: u64 = value.parse().unwrap_or(0);
+                    match unit {
+                        "h" => value * 3600,
+                        "m" => value * 60,
+                        _ => value,
+                    }
+                })
+                .sum();
+        }
+    }
+
+    if let Some((_, rest)) = response.split_once("wait ") {
+        if rest.contains("before trying again") {
+            let mut words = rest.split_whitespace();
+            let amount = match words.next() {
+                Some("one" | "a") => 1,
+                Some(word) => word.parse().unwrap_or(0),
+                None => 0,
+            };
+            return match words.next() {
+                Some(unit) if unit.starts_with("minute") => amount * 60,
+                Some(unit) if unit.starts_with("second") => amount,
+                _ => 0,
+            };
+        }
+    }
+
+    0
+}
+
+/// Rejects answers that the server can not accept: empty and multi-line answers.
+pub fn validate(answer: &str) -> Result<(), String> {
+    if answer.trim().is_empty() {
+        return Err("the answer is empty.".into());
+    }
+
+    if answer.contains('\n') {
+        return Err(
+            "the answer spans multiple lines, read it from the output and submit it manually."
+                .into(),
+        );
+    }
+
+    Ok(())
+}
+
+/// Reasons an answer is probably wrong, `example_answer` is the answer of the example in the puzzle description.
+pub fn warnings(answer: &str, example_answer: Option<&str>) -> Vec<String> {
+    let mut warnings = vec![];
+
+    if answer.trim() == "0" {
+        warnings.push("the answer is 0, which is often a placeholder.".into());
+    }
+
+    if example_answer.is_some_and(|example| example.trim() == answer.trim()) {
+        warnings.push("the answer equals the answer of the example.".into());
+    }
+
+    warnings
+}
+
+/// Asks for confirmation on the terminal. Returns `Err` if there is no terminal to ask on.
+pub fn confirm(question: &str) -> Result<bool, String> {
+    let stdin = io::stdin();
+    if !stdin.is_terminal() {
+        return Err(
+            "can not ask for confirmation without a terminal, pass --yes to submit.".into(),
+        );
+    }
+
+    print!("{question} [y/N] ");
+    let _ = io::stdout().flush();
+
+    let mut reply = String::new();
+    stdin
+        .lock()
+        .read_line(&mut reply)
+        .map_err(|e| e.to_string())?;
+
+    Ok(matches!(reply.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "year".into(),
+            value
+                .year
+                .map_or(JsonValue::Null, |year| JsonValue::Number(year.into())),
+        );
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(value.part.into()));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert("response".into(), JsonValue::String(value.response.clone()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&str> for Submission {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON."))?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected submission to be a JSON object.")?;
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!("expected submission.{key} to be a string."))
+        };
+        let number = |key: &str| json.get(key).and_then(|v| v.get::<f64>()).copied();
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(Submission {
+            year: number("year").map(|year| year as u16),
+            day: Day::from_str(&string("day")?).map_err(|e| e.to_string())?,
+            part: number("part").ok_or("expected submission.part to be a number.")? as u8,
+            answer: string("answer")?,
+            timestamp: number("timestamp").ok_or("expected submission.timestamp to be a number.")?
+                as u64,
+            response: string("response")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_cooldown, remaining_cooldown, validate, warnings, Submission};
+    use crate::day;
+
+    fn submission(day: u8, timestamp: u64, response: &str) -> Submission {
+        Submission {
+            year: Some(2024),
+            day: crate::template::Day::new(day).unwrap(),
+            part: 1,
+            answer: "42".into(),
+            timestamp,
+            response: response.into(),
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_answers() {
+        assert!(validate("1234").is_ok());
+        assert!(validate("").is_err());
+        assert!(validate("  ").is_err());
+        assert!(validate("#..#\n.##.").is_err());
+    }
+
+    #[test]
+    fn warns_on_suspicious_answers() {
+        assert!(warnings("1234", Some("11")).is_empty());
+        assert!(warnings("1234", None).is_empty());
+        assert_eq!(warnings("0", None).len(), 1);
+        assert_eq!(warnings("11", Some("11")).len(), 1);
+    }
+
+    #[test]
+    fn parses_cooldowns() {
+        assert_eq!(parse_cooldown("That's the right answer!"), 0);
+        assert_eq!(
+            parse_cooldown(
+                "That's not the right answer.  Please wait one minute before trying again."
+            ),
+            60
+        );
+        assert_eq!(
+            parse_cooldown(
+                "That's not the right answer; please wait 5 minutes before trying again."
+            ),
+            300
+        );
+        assert_eq!(
+            parse_cooldown("You gave an answer too recently.  You have 1m 54s left to wait."),
+            114
+        );
+    }
+
+    #[test]
+    fn parses_wrapped_cooldowns() {
+        assert_eq!(
+            parse_cooldown("You gave an answer too recently.  You have 1m\n5s left\nto wait."),
+            65
+        );
+        assert_eq!(parse_cooldown("You have 1h 2m 3s left to  wait."), 3723);
+        assert_eq!(
+            parse_cooldown(
+                "That's not the right answer.  Please\nwait 5\nminutes before trying\nagain."
+            ),
+            300
+        );
+    }
+
+    #[test]
+    fn computes_remaining_cooldown() {
+        let log = vec![
+            submission(1, 1_000, "Please wait one minute before trying again."),
+            submission(2, 1_000, "That's the right answer!"),
+        ];
+
+        assert_eq!(
+            remaining_cooldown(&log, Some(2024), day!(1), 1_010),
+            Some(50)
+        );
+        assert_eq!(remaining_cooldown(&log, Some(2024), day!(1), 1_060), None);
+        assert_eq!(remaining_cooldown(&log, Some(2024), day!(2), 1_010), None);
+        assert_eq!(remaining_cooldown(&log, Some(2023), day!(1), 1_010), None);
+    }
+
+    #[test]
+    fn round_trips_submissions() {
+        let submission = submission(3, 1_733_011_200, "You have 54s left to wait.\nOr \"not\".");
+        let line = tinyjson::JsonValue::from(&submission).stringify().unwrap();
+        assert!(!line.contains('\n'));
+        assert_eq!(Submission::try_from(line.as_str()).unwrap(), submission);
+    }
+}
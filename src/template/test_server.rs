@@ -0,0 +1,272 @@
+/// A fake Advent of Code server on localhost, used to test the template without network access.
+///
+/// Every day has the same puzzle: the sum of the numbers in the input, `6` for the example and `42` for the input.
+/// Private leaderboards all have the same three members, the personal stats show stars for three days.
+/// Submissions are answered like the real server: the right answer, too high, too low or, after a wrong answer,
+/// a rate limit for the next submission.
+///
+/// Leaderboards and stats are requested by the HTTP client. Puzzles, inputs and submissions go through aoc-cli,
+/// which is replaced by a fake `aoc` executable, see [`fake_aoc_cli`]. As the template only sees what aoc-cli
+/// writes and prints, puzzle descriptions and submission responses are served as that text instead of html.
+///
+/// Tests that write files use a fresh directory from [`temp_dir`], or [`TestServer::with_temp_dir`] with a server.
+use std::{
+    env, fs,
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::PathBuf,
+    process,
+    sync::{Arc, Mutex, MutexGuard, OnceLock},
+    thread,
+};
+
+pub const INPUT: &str = "40\n2\n";
+pub const ANSWER: i64 = 42;
+
+/// Every private leaderboard, in the format of the real server.
+pub const LEADERBOARD: &str = r#"{
+  "owner_id": 1,
+  "event": "2024",
+  "members": {
+    "1": {
+      "id": 1, "name": "Alice", "stars": 3, "local_score": 6, "global_score": 0, "last_star_ts": 1733115661,
+      "completion_day_level": {
+        "1": {"1": {"get_star_ts": 1733029261, "star_index": 1}, "2": {"get_star_ts": 1733029500, "star_index": 5}},
+        "2": {"1": {"get_star_ts": 1733115661, "star_index": 9}}
+      }
+    },
+    "2": {
+      "id": 2, "name": "Bob", "stars": 1, "local_score": 1, "global_score": 0, "last_star_ts": 1733119261,
+      "completion_day_level": {"2": {"1": {"get_star_ts": 1733119261, "star_index": 12}}}
+    },
+    "3": {
+      "id": 3, "name": null, "stars": 2, "local_score": 4, "global_score": 0, "last_star_ts": 1733030000,
+      "completion_day_level": {
+        "1": {"1": {"get_star_ts": 1733029400, "star_index": 3}, "2": {"get_star_ts": 1733030000, "star_index": 7}}
+      }
+    }
+  }
+}"#;
+
+/// The personal stats page, in the format of the real server.
+pub const STATS: &str = r#"<!DOCTYPE html>
+<html lang="en-us">
+<body>
+<main>
+<article><p>These are your personal leaderboard statistics. <em>Rank</em> is your position on that leaderboard.</p>
+<pre><span class="leaderboard-daydesc-first">      --------Part 1---------   </span><span class="leaderboard-daydesc-both">--------Part 2---------</span>
+Day <span class="leaderboard-daydesc-first">      Time    Rank  Score   </span><span class="leaderboard-daydesc-both">      Time    Rank  Score</span>
+  3       &gt;24h   23456      0          -       -      -
+  2   01:01:01    4321      0   01:30:00    3210      0
+  1   00:01:01      98      3   00:05:00    1234      0
+</pre>
+</article>
+</main>
+</body>
+</html>"#;
+
+/// Stand-in for aoc-cli: takes the same arguments and requests the server at `AOC_TEST_SERVER` with curl.
+const FAKE_AOC_CLI: &str = r#"#!/bin/sh
+set -e
+while [ $# -gt 0 ]; do
+    case $1 in
+        -V) echo "aoc-cli (fake)"; exit 0 ;;
+        --year) year=$2; shift ;;
+        --day) day=${2#0}; shift ;;
+        --input-file) input=$2; shift ;;
+        --puzzle-file) puzzle=$2; shift ;;
+        --overwrite | --description-only) ;;
+        *) break ;;
+    esac
+    shift
+done
+
+url="$AOC_TEST_SERVER/$year/day/$day"
+case $1 in
+    download) curl -sSf -o "$input" "$url/input"; curl -sSf -o "$puzzle" "$url" ;;
+    read) curl -sSf -o "$puzzle" "$url"; cat "$puzzle" ;;
+    submit) curl -sSf --data "level=$2&answer=$3" "$url/answer" ;;
+    *) echo "unexpected arguments: $*" >&2; exit 2 ;;
+esac
+"#;
+
+pub struct TestServer {
+    /// base url to configure as `server.url`.
+    pub url: String,
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Default)]
+struct State {
+    /// a wrong answer makes the next submission hit the rate limit.
+    cooldown: bool,
+    /// request lines and bodies received, e.g. `POST /2024/day/1/answer level=1&answer=42`.
+    requests: Vec<String>,
+}
+
+impl TestServer {
+    /// Starts the server on a free port, it runs until the test process exits.
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let state = Arc::new(Mutex::new(State::default()));
+
+        let server_state = Arc::clone(&state);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let _ = handle(stream, &server_state);
+            }
+        });
+
+        Self { url, state }
+    }
+
+    /// Starts a server along with a fresh temporary directory for the test `name`.
+    pub fn with_temp_dir(name: &str) -> (Self, PathBuf) {
+        (Self::start(), temp_dir(name))
+    }
+
+    pub fn requests(&self) -> Vec<String> {
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+/// Puts the fake aoc-cli in front of `PATH`, talking to the server at `url` until the returned guard is dropped.
+/// `PATH` and the server url are shared by the whole process, so tests calling aoc-cli run one at a time.
+pub fn fake_aoc_cli(url: &str) -> MutexGuard<'static, ()> {
+    static LOCK: Mutex<()> = Mutex::new(());
+    static INSTALLED: OnceLock<()> = OnceLock::new();
+
+    let guard = LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+    INSTALLED.get_or_init(|| {
+        let dir = temp_dir("fake_aoc_cli");
+        let path = dir.join("aoc");
+        fs::write(&path, FAKE_AOC_CLI).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        }
+
+        let paths = env::var_os("PATH").unwrap_or_default();
+        let paths = env::join_paths(std::iter::once(dir).chain(env::split_paths(&paths))).unwrap();
+        env::set_var("PATH", paths);
+    });
+
+    env::set_var("AOC_TEST_SERVER", url);
+    guard
+}
+
+/// An empty temporary directory for the test `name`, unique to the test process.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aoc_{name}_{}", process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn handle(mut stream: TcpStream, state: &Mutex<State>) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        if line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    let body = String::from_utf8_lossy(&body).into_owned();
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts.next().unwrap_or_default();
+
+    let (status, response) = respond(method, path, &body, &mut state.lock().unwrap());
+
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+        response.len()
+    )
+}
+
+fn respond(method: &str, path: &str, body: &str, state: &mut State) -> (&'static str, String) {
+    state
+        .requests
+        .push(format!("{method} {path} {body}").trim().into());
+
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+    match (method, segments.as_slice()) {
+        ("GET", [_, "day", day]) => ("200 OK", puzzle_description(day)),
+        ("GET", [_, "day", _, "input"]) => ("200 OK", INPUT.into()),
+        ("GET", [_, "leaderboard", "private", "view", id]) if id.ends_with(".json") => {
+            ("200 OK", LEADERBOARD.into())
+        }
+        ("GET", [_, "leaderboard", "self"]) => ("200 OK", STATS.into()),
+        ("POST", [_, "day", _, "answer"]) => ("200 OK", submit(body, state)),
+        _ => ("404 Not Found", "404 Not Found".into()),
+    }
+}
+
+fn submit(body: &str, state: &mut State) -> String {
+    let answer = body
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("answer="))
+        .unwrap_or_default();
+
+    if state.cooldown {
+        state.cooldown = false;
+        return "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 54s left to wait.".into();
+    }
+
+    match answer.parse::<i64>() {
+        Ok(ANSWER) => {
+            "That's the right answer!  You are one gold star closer to saving your vacation.".into()
+        }
+        Ok(answer) => {
+            state.cooldown = true;
+            let direction = if answer > ANSWER { "high" } else { "low" };
+            format!("That's not the right answer; your answer is too {direction}.  If you're stuck, make sure you're using the full input data.  Please wait one minute before trying again.")
+        }
+        Err(_) => {
+            state.cooldown = true;
+            "That's not the right answer.  If you're stuck, make sure you're using the full input data.  Please wait one minute before trying again.".into()
+        }
+    }
+}
+
+/// The description as aoc-cli writes it to the puzzle file.
+fn puzzle_description(day: &str) -> String {
+    let day = day.trim_start_matches('0');
+    format!(
+        r"\--- Day {day}: Test & Sum ---
+----------
+
+Add up the numbers. For example:
+
+```
+1
+2
+3
+```
+
+In this example, the sum is `*6*`.
+"
+    )
+}
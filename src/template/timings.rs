@@ -1,9 +1,8 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fmt::Display, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
-
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+use crate::template::alloc::AllocCount;
+use crate::template::{read_if_exists, Day};
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
@@ -12,6 +11,159 @@ pub struct Timing {
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    pub part_1_heap: Option<HeapSummary>,
+    pub part_2_heap: Option<HeapSummary>,
+    /// mean allocations per run, as counted with `--count-allocs`.
+    pub part_1_allocs: Option<AllocCount>,
+    pub part_2_allocs: Option<AllocCount>,
+    pub part_1_cost: Option<Cost>,
+    pub part_2_cost: Option<Cost>,
+    /// duration of the separate parse step, if the solution has one.
+    pub parse: Option<String>,
+    pub part_1_samples: Option<u64>,
+    pub part_2_samples: Option<u64>,
+    pub part_1_stddev: Option<String>,
+    pub part_2_stddev: Option<String>,
+}
+
+/// Represents the heap usage of a single part, as measured by DHAT.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HeapSummary {
+    pub total_bytes: u64,
+    pub peak_bytes: u64,
+    pub allocations: u64,
+}
+
+impl Display for HeapSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} B total, {} B peak, {} allocs",
+            self.total_bytes, self.peak_bytes, self.allocations
+        )
+    }
+}
+
+impl FromStr for HeapSummary {
+    type Err = String;
+
+    /// Parses the format written by [`Display`], e.g. `1024 B total, 512 B peak, 3 allocs`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut values = s.split(',').map(|x| {
+            x.split_whitespace()
+                .next()
+                .and_then(|n| n.parse::<u64>().ok())
+                .ok_or_else(|| format!("could not parse heap summary: {s}"))
+        });
+
+        let mut next = || {
+            values
+                .next()
+                .unwrap_or_else(|| Err(format!("incomplete heap summary: {s}")))
+        };
+
+        Ok(HeapSummary {
+            total_bytes: next()?,
+            peak_bytes: next()?,
+            allocations: next()?,
+        })
+    }
+}
+
+/// Unit of a [`Cost`] measurement.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CostUnit {
+    /// Retired CPU instructions, as read from hardware performance counters.
+    Instructions,
+    /// Units of work reported by the solution via `template::cost::count`.
+    Iterations,
+}
+
+impl CostUnit {
+    fn as_str(self) -> &'static str {
+        match self {
+            CostUnit::Instructions => "instructions",
+            CostUnit::Iterations => "iterations",
+        }
+    }
+}
+
+impl FromStr for CostUnit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "instructions" => Ok(CostUnit::Instructions),
+            "iterations" => Ok(CostUnit::Iterations),
+            _ => Err(format!("unknown cost unit: {s}")),
+        }
+    }
+}
+
+/// Represents the hardware-independent cost of a single part.
+/// Unlike durations, this is stable across runs and machines.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cost {
+    pub value: u64,
+    pub unit: CostUnit,
+}
+
+impl Display for Cost {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.value, self.unit.as_str())
+    }
+}
+
+impl FromStr for Cost {
+    type Err = String;
+
+    /// Parses the format written by [`Display`], e.g. `123456 instructions`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (value, unit) = s
+            .trim()
+            .split_once(' ')
+            .ok_or_else(|| format!("could not parse cost: {s}"))?;
+
+        Ok(Cost {
+            value: value
+                .parse()
+                .map_err(|_| format!("could not parse cost: {s}"))?,
+            unit: unit.parse()?,
+        })
+    }
+}
+
+/// Formats a byte count with a binary unit prefix, e.g. `1.50 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.2} {}", UNITS[unit])
+}
+
+/// Parses a duration as printed by the runner, e.g. `74.1ms`, to nanoseconds.
+pub fn parse_duration(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    let parse_to_float = |postfix: &str| s.split(postfix).next()?.parse::<f64>().ok();
+
+    match s {
+        s if s.contains("ns") => parse_to_float("ns"),
+        s if s.contains("µs") => parse_to_float("µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float("ms").map(|x| x * 1_000_000_f64),
+        _ => parse_to_float("s").map(|x| x * 1_000_000_000_f64),
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -22,34 +174,40 @@ pub struct Timings {
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Dehydrate timings to a JSON file at `path`.
+    pub fn store_file(&self, path: &str) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(path)?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from);
-
-        match s {
-            Ok(timings) => timings,
-            Err(e) => {
-                eprintln!("{e}");
-                Timings::default()
-            }
+    /// Rehydrate timings from a JSON file at `path`. If not present, returns empty timings.
+    /// Errors if the file can not be read or parsed, so that it is not overwritten.
+    pub fn read_from_path(path: &str) -> Result<Self, String> {
+        match read_if_exists(path)? {
+            Some(s) => Timings::try_from(s).map_err(|e| format!("{path}: {e}")),
+            None => Ok(Timings::default()),
         }
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Heap summaries, allocation counts and costs are kept if the new timing does not contain them.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let mut timing = timing.clone();
+
+            if let Some(stored) = self.data.iter().find(|t| t.day == timing.day) {
+                timing.part_1_heap = timing.part_1_heap.or(stored.part_1_heap);
+                timing.part_2_heap = timing.part_2_heap.or(stored.part_2_heap);
+                timing.part_1_allocs = timing.part_1_allocs.or(stored.part_1_allocs);
+                timing.part_2_allocs = timing.part_2_allocs.or(stored.part_2_allocs);
+                timing.part_1_cost = timing.part_1_cost.or(stored.part_1_cost);
+                timing.part_2_cost = timing.part_2_cost.or(stored.part_2_cost);
+            }
+
+            data.push(timing);
         }
 
         for timing in &self.data {
@@ -62,6 +220,43 @@ impl Timings {
         Timings { data }
     }
 
+    /// Merge the heap summaries and allocation counts of `new` into `self`, keeping the stored durations.
+    /// Used for profiling runs, where durations are skewed by the profiler or the counting allocator.
+    pub fn merge_allocations(&self, new: &Self) -> Self {
+        let mut data = self.data.clone();
+
+        for timing in &new.data {
+            match data.iter_mut().find(|t| t.day == timing.day) {
+                Some(stored) => {
+                    stored.part_1_heap = timing.part_1_heap.or(stored.part_1_heap);
+                    stored.part_2_heap = timing.part_2_heap.or(stored.part_2_heap);
+                    stored.part_1_allocs = timing.part_1_allocs.or(stored.part_1_allocs);
+                    stored.part_2_allocs = timing.part_2_allocs.or(stored.part_2_allocs);
+                }
+                None => data.push(Timing {
+                    day: timing.day,
+                    part_1: None,
+                    part_2: None,
+                    total_nanos: 0_f64,
+                    part_1_heap: timing.part_1_heap,
+                    part_2_heap: timing.part_2_heap,
+                    part_1_allocs: timing.part_1_allocs,
+                    part_2_allocs: timing.part_2_allocs,
+                    part_1_cost: None,
+                    part_2_cost: None,
+                    parse: None,
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    part_1_stddev: None,
+                    part_2_stddev: None,
+                }),
+            }
+        }
+
+        data.sort_unstable_by_key(|t| t.day);
+        Timings { data }
+    }
+
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
@@ -140,6 +335,53 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        if let Some(heap) = value.part_1_heap {
+            map.insert("part_1_heap".into(), JsonValue::from(heap));
+        }
+
+        if let Some(heap) = value.part_2_heap {
+            map.insert("part_2_heap".into(), JsonValue::from(heap));
+        }
+
+        if let Some(allocs) = value.part_1_allocs {
+            map.insert("part_1_allocs".into(), JsonValue::from(allocs));
+        }
+
+        if let Some(allocs) = value.part_2_allocs {
+            map.insert("part_2_allocs".into(), JsonValue::from(allocs));
+        }
+
+        if let Some(cost) = value.part_1_cost {
+            map.insert("part_1_cost".into(), JsonValue::from(cost));
+        }
+
+        if let Some(cost) = value.part_2_cost {
+            map.insert("part_2_cost".into(), JsonValue::from(cost));
+        }
+
+        if let Some(parse) = &value.parse {
+            map.insert("parse".into(), JsonValue::String(parse.clone()));
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        for (key, samples) in [
+            ("part_1_samples", value.part_1_samples),
+            ("part_2_samples", value.part_2_samples),
+        ] {
+            if let Some(samples) = samples {
+                map.insert(key.into(), JsonValue::Number(samples as f64));
+            }
+        }
+
+        for (key, stddev) in [
+            ("part_1_stddev", &value.part_1_stddev),
+            ("part_2_stddev", &value.part_2_stddev),
+        ] {
+            if let Some(stddev) = stddev {
+                map.insert(key.into(), JsonValue::String(stddev.clone()));
+            }
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -173,17 +415,195 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // heap summaries are optional, timings stored without profiling do not contain them.
+        let part_1_heap = json
+            .get("part_1_heap")
+            .map(HeapSummary::try_from)
+            .transpose()?;
+
+        let part_2_heap = json
+            .get("part_2_heap")
+            .map(HeapSummary::try_from)
+            .transpose()?;
+
+        // as are allocation counts, they are only measured with the `count-allocs` feature.
+        let part_1_allocs = json
+            .get("part_1_allocs")
+            .map(AllocCount::try_from)
+            .transpose()?;
+
+        let part_2_allocs = json
+            .get("part_2_allocs")
+            .map(AllocCount::try_from)
+            .transpose()?;
+
+        // costs are optional as well, they are only measured with the `cost-counters` feature.
+        let part_1_cost = json.get("part_1_cost").map(Cost::try_from).transpose()?;
+        let part_2_cost = json.get("part_2_cost").map(Cost::try_from).transpose()?;
+
+        // benchmark details were added later and are missing from older timings.
+        let get_string = |key: &str| json.get(key).and_then(|v| v.get::<String>()).cloned();
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let get_number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|v| *v as u64)
+        };
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            part_1_heap,
+            part_2_heap,
+            part_1_allocs,
+            part_2_allocs,
+            part_1_cost,
+            part_2_cost,
+            parse: get_string("parse"),
+            part_1_samples: get_number("part_1_samples"),
+            part_2_samples: get_number("part_2_samples"),
+            part_1_stddev: get_string("part_1_stddev"),
+            part_2_stddev: get_string("part_2_stddev"),
         })
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
+impl From<HeapSummary> for JsonValue {
+    fn from(value: HeapSummary) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert(
+                "total_bytes".into(),
+                JsonValue::Number(value.total_bytes as f64),
+            );
+            map.insert(
+                "peak_bytes".into(),
+                JsonValue::Number(value.peak_bytes as f64),
+            );
+            map.insert(
+                "allocations".into(),
+                JsonValue::Number(value.allocations as f64),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HeapSummary {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected heap summary to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let get_number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|v| *v as u64)
+                .ok_or(format!("Expected heap summary.{key} to be a number."))
+        };
+
+        Ok(HeapSummary {
+            total_bytes: get_number("total_bytes")?,
+            peak_bytes: get_number("peak_bytes")?,
+            allocations: get_number("allocations")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<AllocCount> for JsonValue {
+    fn from(value: AllocCount) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert(
+                "allocations".into(),
+                JsonValue::Number(value.allocations as f64),
+            );
+            map.insert("bytes".into(), JsonValue::Number(value.bytes as f64));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for AllocCount {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected allocation count to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let get_number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|v| *v as u64)
+                .ok_or(format!("Expected allocation count.{key} to be a number."))
+        };
+
+        Ok(AllocCount {
+            allocations: get_number("allocations")?,
+            bytes: get_number("bytes")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Cost> for JsonValue {
+    fn from(value: Cost) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("value".into(), JsonValue::Number(value.value as f64));
+        map.insert("unit".into(), JsonValue::String(value.unit.as_str().into()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Cost {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected cost to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let value = json
+            .get("value")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected cost.value to be a number.")?;
+
+        let unit = json
+            .get("unit")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected cost.unit to be a string.")?
+            .parse()?;
+
+        Ok(Cost { value, unit })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 #[cfg(feature = "test_lib")]
 mod tests {
     use crate::day;
@@ -198,18 +618,51 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    part_1_cost: None,
+                    part_2_cost: None,
+                    parse: None,
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    part_1_stddev: None,
+                    part_2_stddev: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    part_1_cost: None,
+                    part_2_cost: None,
+                    parse: None,
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    part_1_stddev: None,
+                    part_2_stddev: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    part_1_cost: None,
+                    part_2_cost: None,
+                    parse: None,
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    part_1_stddev: None,
+                    part_2_stddev: None,
                 },
             ],
         }
@@ -254,9 +707,86 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::day;
+        use crate::template::alloc::AllocCount;
+        use crate::template::test_server::temp_dir;
+        use crate::template::timings::{Cost, CostUnit, HeapSummary, Timings};
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
+        #[test]
+        fn round_trips_heap_summaries() {
+            let mut timings = get_mock_timings();
+            timings.data[0].part_2_heap = Some(HeapSummary {
+                total_bytes: 2048,
+                peak_bytes: 1024,
+                allocations: 3,
+            });
+
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].part_1_heap, None);
+            assert_eq!(timings.data[0].part_2_heap.unwrap().peak_bytes, 1024);
+        }
+
+        #[test]
+        fn round_trips_allocation_counts() {
+            let mut timings = get_mock_timings();
+            timings.data[1].part_1_allocs = Some(AllocCount {
+                allocations: 3,
+                bytes: 276,
+            });
+
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[1].part_1_allocs.unwrap().bytes, 276);
+            assert_eq!(timings.data[1].part_2_allocs, None);
+        }
+
+        #[test]
+        fn round_trips_benchmark_details() {
+            let mut timings = get_mock_timings();
+            timings.data[0].parse = Some("1.2ms".into());
+            timings.data[0].part_1_samples = Some(243);
+            timings.data[0].part_1_stddev = Some("3.4µs".into());
+            timings.data[0].part_2_cost = Some(Cost {
+                value: 1234,
+                unit: CostUnit::Iterations,
+            });
+
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].parse.as_deref(), Some("1.2ms"));
+            assert_eq!(timings.data[0].part_1_samples, Some(243));
+            assert_eq!(timings.data[0].part_2_samples, None);
+            assert_eq!(timings.data[0].part_1_stddev.as_deref(), Some("3.4µs"));
+            assert_eq!(
+                timings.data[0].part_2_cost.unwrap().to_string(),
+                "1234 iterations"
+            );
+        }
+
+        #[test]
+        fn keeps_days_of_any_event() {
+            let mut timings = get_mock_timings();
+            timings.data[2].day = day!(20);
+
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[2].day, day!(20));
+        }
+
+        #[test]
+        fn refuses_unreadable_files() {
+            let path = temp_dir("timings").join("timings.json");
+            let path = path.to_string_lossy();
+
+            assert!(Timings::read_from_path(&path).unwrap().data.is_empty());
+
+            std::fs::write(&*path, "{ not json").unwrap();
+            assert!(Timings::read_from_path(&path).is_err());
+        }
+
         #[test]
         fn serializes_timings() {
             let timings = get_mock_timings();
@@ -275,6 +805,38 @@ mod tests {
         }
     }
 
+    mod heap_summary {
+        use crate::template::alloc::AllocCount;
+        use crate::template::timings::{format_bytes, HeapSummary};
+
+        #[test]
+        fn parses_displayed_summary() {
+            let heap = HeapSummary {
+                total_bytes: 2048,
+                peak_bytes: 1024,
+                allocations: 3,
+            };
+            assert_eq!(heap.to_string().parse::<HeapSummary>(), Ok(heap));
+        }
+
+        #[test]
+        fn round_trips_allocation_count_strings() {
+            let allocs = AllocCount {
+                allocations: 3,
+                bytes: 276,
+            };
+            assert_eq!(allocs.to_string(), "3 allocs, 276 B");
+            assert_eq!(allocs.to_string().parse::<AllocCount>(), Ok(allocs));
+        }
+
+        #[test]
+        fn formats_bytes() {
+            assert_eq!(format_bytes(512), "512 B");
+            assert_eq!(format_bytes(1536), "1.50 KiB");
+            assert_eq!(format_bytes(3 * 1024 * 1024), "3.00 MiB");
+        }
+    }
+
     mod is_day_complete {
         use crate::{
             day,
@@ -289,10 +851,21 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    part_1_cost: None,
+                    part_2_cost: None,
+                    parse: None,
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    part_1_stddev: None,
+                    part_2_stddev: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -303,10 +876,21 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    part_1_cost: None,
+                    part_2_cost: None,
+                    parse: None,
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    part_1_stddev: None,
+                    part_2_stddev: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -317,17 +901,29 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    part_1_cost: None,
+                    part_2_cost: None,
+                    parse: None,
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    part_1_stddev: None,
+                    part_2_stddev: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::alloc::AllocCount,
+            template::timings::{HeapSummary, Timing, Timings},
         };
 
         use super::get_mock_timings;
@@ -341,6 +937,17 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    part_1_cost: None,
+                    part_2_cost: None,
+                    parse: None,
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    part_1_stddev: None,
+                    part_2_stddev: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -361,6 +968,17 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    part_1_heap: None,
+                    part_2_heap: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    part_1_cost: None,
+                    part_2_cost: None,
+                    parse: None,
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    part_1_stddev: None,
+                    part_2_stddev: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -380,6 +998,75 @@ mod tests {
             assert_eq!(merged.data.len(), 3);
         }
 
+        #[test]
+        fn keeps_stored_heap_summaries() {
+            let mut timings = get_mock_timings();
+            timings.data[1].part_1_heap = Some(HeapSummary {
+                total_bytes: 10,
+                peak_bytes: 5,
+                allocations: 1,
+            });
+
+            let merged = timings.merge(&get_mock_timings());
+            assert_eq!(merged.data[1].part_1_heap.unwrap().peak_bytes, 5);
+        }
+
+        #[test]
+        fn merges_heap_summaries_only() {
+            let timings = get_mock_timings();
+            let other = Timings {
+                data: vec![Timing {
+                    day: day!(2),
+                    part_1: Some("1s".into()),
+                    part_2: None,
+                    total_nanos: 1e+9,
+                    part_1_heap: Some(HeapSummary {
+                        total_bytes: 10,
+                        peak_bytes: 5,
+                        allocations: 1,
+                    }),
+                    part_2_heap: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    part_1_cost: None,
+                    part_2_cost: None,
+                    parse: None,
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    part_1_stddev: None,
+                    part_2_stddev: None,
+                }],
+            };
+
+            let merged = timings.merge_allocations(&other);
+            assert_eq!(merged.data.len(), 3);
+            assert_eq!(merged.data[1].part_1, Some("30ms".into()));
+            assert_eq!(merged.data[1].total_nanos, 7e+10);
+            assert_eq!(merged.data[1].part_1_heap.unwrap().allocations, 1);
+        }
+
+        #[test]
+        fn merges_allocation_counts_only() {
+            let mut timings = get_mock_timings();
+            timings.data[1].part_2_allocs = Some(AllocCount {
+                allocations: 2,
+                bytes: 64,
+            });
+
+            let mut other = get_mock_timings();
+            other.data.retain(|t| t.day == day!(2));
+            other.data[0].total_nanos = 1e+9;
+            other.data[0].part_1_allocs = Some(AllocCount {
+                allocations: 7,
+                bytes: 128,
+            });
+
+            let merged = timings.merge_allocations(&other);
+            assert_eq!(merged.data[1].total_nanos, 7e+10);
+            assert_eq!(merged.data[1].part_1_allocs.unwrap().allocations, 7);
+            assert_eq!(merged.data[1].part_2_allocs.unwrap().bytes, 64);
+        }
+
         #[test]
         fn handles_empty_other_timings() {
             let timings = get_mock_timings();
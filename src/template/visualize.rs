@@ -0,0 +1,676 @@
+/// Renders grid simulations frame by frame in the terminal, enabled by passing `--visualize` to a solution.
+///
+/// [`Visualizer::new`] returns `None` without the flag, or when the solution is benched with `--time`,
+/// so a solution only pays for building frames while it is visualized:
+///
+/// ```ignore
+/// let mut visualizer = Visualizer::new().map(|v| v.color('#', Color::Gray).color('^', Color::Yellow));
+///
+/// for step in 0..steps {
+///     // ...
+///     if let Some(visualizer) = &mut visualizer {
+///         visualizer.show(|| Frame::new(&board).highlight(visited.iter().copied()));
+///     }
+/// }
+/// ```
+///
+/// While frames are shown, `space` pauses and resumes, `n` steps to the next frame while paused,
+/// `+` and `-` change the speed and `q` skips to the last frame.
+///
+/// With `--export gif` or `--export png`, the frames are also written to `paths.visualizations`
+/// as an animated GIF or a sequence of PNG files, see [`crate::template::frame_export`].
+/// Without `--visualize`, frames are exported headless and not drawn in the terminal.
+use std::{
+    collections::{HashMap, HashSet},
+    env,
+    fmt::Display,
+    fs::File,
+    io::{self, IsTerminal, Read, Write},
+    process::{Command, Stdio},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "visualize-export")]
+use crate::template::frame_export::{Exporter, Paint};
+use crate::template::{Config, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const DEFAULT_DELAY: Duration = Duration::from_millis(50);
+const MAX_DELAY: Duration = Duration::from_secs(2);
+
+/// Frames exported per visualization, later frames are left out except for the last one.
+#[cfg(feature = "visualize-export")]
+const MAX_EXPORTED_FRAMES: usize = 2000;
+
+/// The part that is currently run, used to name exported files.
+static PART: Mutex<Option<RunningPart>> = Mutex::new(None);
+
+#[derive(Clone)]
+#[cfg_attr(not(feature = "visualize-export"), allow(dead_code))]
+struct RunningPart {
+    day: Day,
+    part: u8,
+    /// directory of exported files, see `paths.visualizations` of the [`Config`].
+    visualizations: String,
+}
+
+/// Whether the solution was started with `--visualize` or `--export` and is not benched.
+pub fn is_enabled() -> bool {
+    (is_drawn() || export_format().is_some()) && !env::args().any(|x| x == "--time")
+}
+
+fn is_drawn() -> bool {
+    env::args().any(|x| x == "--visualize")
+}
+
+/// The format passed with `--export`, if any.
+fn export_format() -> Option<ExportFormat> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--export")?;
+    args.get(index + 1)?.parse().ok()
+}
+
+/// Remember the part that is run, called by the runner before a part is solved.
+pub(crate) fn set_part(day: Day, part: u8, config: &Config) {
+    if let Ok(mut current) = PART.lock() {
+        *current = Some(RunningPart {
+            day,
+            part,
+            visualizations: config.paths.visualizations.clone(),
+        });
+    }
+}
+
+/// Image formats frames can be exported to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// a single animated GIF.
+    Gif,
+    /// one PNG file per frame.
+    Png,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "gif" => Ok(ExportFormat::Gif),
+            "png" => Ok(ExportFormat::Png),
+            _ => Err(format!(
+                "unknown export format `{s}`, expected `gif` or `png`."
+            )),
+        }
+    }
+}
+
+impl Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ExportFormat::Gif => "gif",
+            ExportFormat::Png => "png",
+        })
+    }
+}
+
+/// Terminal colours for cells.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Gray,
+}
+
+impl Color {
+    /// ANSI code of the foreground colour, the background colour is offset by 10.
+    fn code(self) -> u8 {
+        match self {
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+            Color::White => 37,
+            Color::Gray => 90,
+        }
+    }
+}
+
+/// A single frame: a grid of characters, highlighted cells and an optional caption.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Frame {
+    pub cells: Vec<Vec<char>>,
+    /// cells drawn with the highlight colour as background, by `(x, y)`.
+    pub highlighted: HashSet<(usize, usize)>,
+    pub caption: Option<String>,
+}
+
+impl Frame {
+    /// A frame of the given rows, e.g. a `Vec<Vec<char>>` board or the lines of a string.
+    pub fn new<R: AsRef<[char]>>(rows: &[R]) -> Self {
+        Self {
+            cells: rows.iter().map(|row| row.as_ref().to_vec()).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// A frame of `width` by `height` cells, with the character of each cell at `(x, y)` given by `cell`.
+    pub fn from_fn(width: usize, height: usize, cell: impl Fn(usize, usize) -> char) -> Self {
+        Self {
+            cells: (0..height)
+                .map(|y| (0..width).map(|x| cell(x, y)).collect())
+                .collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Replace the character at `(x, y)`, cells outside of the frame are ignored.
+    pub fn set(&mut self, x: usize, y: usize, ch: char) {
+        if let Some(cell) = self.cells.get_mut(y).and_then(|row| row.get_mut(x)) {
+            *cell = ch;
+        }
+    }
+
+    /// Highlight cells, e.g. the cells of a path, by `(x, y)`.
+    #[must_use]
+    pub fn highlight(mut self, cells: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        self.highlighted.extend(cells);
+        self
+    }
+
+    #[must_use]
+    pub fn caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = Some(caption.into());
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.len()
+    }
+}
+
+/// Key presses that control the playback.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Control {
+    TogglePause,
+    Step,
+    Faster,
+    Slower,
+    Skip,
+}
+
+impl Control {
+    fn from_key(key: u8) -> Option<Self> {
+        match key {
+            b' ' | b'p' => Some(Control::TogglePause),
+            b'n' | b'.' => Some(Control::Step),
+            b'+' | b'=' => Some(Control::Faster),
+            b'-' => Some(Control::Slower),
+            b'q' => Some(Control::Skip),
+            _ => None,
+        }
+    }
+}
+
+/// Switches the terminal to unbuffered input without echo, so single key presses are read.
+/// Reads return after a tenth of a second without a key press, so a reader can stop in time.
+/// The previous settings are restored when dropped.
+struct RawTerminal {
+    settings: String,
+}
+
+impl RawTerminal {
+    fn stty(args: &[&str]) -> Option<String> {
+        let output = Command::new("stty")
+            .args(args)
+            .stdin(File::open("/dev/tty").ok()?)
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    fn enable() -> Option<Self> {
+        let settings = Self::stty(&["-g"])?;
+        Self::stty(&["-icanon", "-echo", "min", "0", "time", "1"])?;
+        Some(Self { settings })
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        Self::stty(&[&self.settings]);
+    }
+}
+
+/// Key presses read by a background thread. The thread stops when dropped,
+/// so that it does not take input meant for the solution or the runner, e.g. the `--submit` confirmation.
+struct Controls {
+    receiver: Receiver<Control>,
+    stop: Arc<AtomicBool>,
+    reader: Option<JoinHandle<()>>,
+    /// restored after the reader stopped, fields are dropped after `drop` ran.
+    _terminal: RawTerminal,
+}
+
+impl Controls {
+    /// Reads key presses from the terminal, if stdin is one.
+    fn start() -> Option<Self> {
+        if !io::stdin().is_terminal() {
+            return None;
+        }
+        let terminal = RawTerminal::enable()?;
+        // keys are read from the terminal directly, the buffer of stdin would keep keys read ahead.
+        let mut tty = File::open("/dev/tty").ok()?;
+
+        let (sender, receiver) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let reader_stop = Arc::clone(&stop);
+
+        let reader = thread::spawn(move || {
+            let mut key = [0];
+            while !reader_stop.load(Ordering::Relaxed) {
+                match tty.read(&mut key) {
+                    // no key was pressed before the read timed out.
+                    Ok(0) => {}
+                    Ok(_) => {
+                        if let Some(control) = Control::from_key(key[0]) {
+                            if sender.send(control).is_err() {
+                                break;
+                            }
+                        }
+                    }
+                    Err(_) => break,
+                }
+            }
+        });
+
+        Some(Self {
+            receiver,
+            stop,
+            reader: Some(reader),
+            _terminal: terminal,
+        })
+    }
+}
+
+impl Drop for Controls {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(reader) = self.reader.take() {
+            let _ = reader.join();
+        }
+    }
+}
+
+pub struct Visualizer {
+    colors: HashMap<char, Color>,
+    highlight: Color,
+    delay: Duration,
+    paused: bool,
+    skipped: bool,
+    frames: usize,
+    /// whether frames are drawn in the terminal, they may only be exported.
+    draws: bool,
+    controls: Option<Controls>,
+    #[cfg(feature = "visualize-export")]
+    exporter: Option<Exporter>,
+}
+
+impl Visualizer {
+    /// A visualizer if the solution was started with `--visualize` or `--export`, see [`is_enabled`].
+    pub fn new() -> Option<Self> {
+        if !is_enabled() {
+            return None;
+        }
+
+        let mut visualizer = if is_drawn() {
+            Self::with_controls()
+        } else {
+            let mut visualizer = Self::default();
+            visualizer.draws = false;
+            visualizer
+        };
+
+        if let Some(format) = export_format() {
+            visualizer.export(format);
+        }
+
+        Some(visualizer)
+    }
+
+    /// A visualizer that reads key presses from stdin, if it is a terminal.
+    fn with_controls() -> Self {
+        let mut visualizer = Self::default();
+        visualizer.controls = Controls::start();
+        visualizer
+    }
+
+    #[cfg(feature = "visualize-export")]
+    fn export(&mut self, format: ExportFormat) {
+        let (name, dir) = match PART.lock().ok().and_then(|part| part.clone()) {
+            Some(RunningPart {
+                day,
+                part,
+                visualizations,
+            }) => (format!("{day}-part{part}"), visualizations),
+            None => ("frames".into(), Config::default().paths.visualizations),
+        };
+        self.exporter = Some(Exporter::new(format, dir, name));
+    }
+
+    #[cfg(not(feature = "visualize-export"))]
+    fn export(&mut self, _: ExportFormat) {
+        eprintln!("Exporting frames requires the `visualize-export` feature, which `cargo solve <day> --export <format>` enables.");
+    }
+
+    /// Draw `ch` in `color`. Characters without a colour use the default colour of the terminal.
+    #[must_use]
+    pub fn color(mut self, ch: char, color: Color) -> Self {
+        self.colors.insert(ch, color);
+        self
+    }
+
+    /// Background colour of highlighted cells, yellow by default.
+    #[must_use]
+    pub fn highlight(mut self, color: Color) -> Self {
+        self.highlight = color;
+        self
+    }
+
+    /// Initial number of frames shown per second, can be changed with `+` and `-`.
+    #[must_use]
+    pub fn fps(mut self, fps: u32) -> Self {
+        self.delay = Duration::from_secs(1) / fps.max(1);
+        self
+    }
+
+    /// Show a frame and wait until the next one is due. While paused, waits for a key press.
+    /// The frame is only built if it is drawn or exported, frames are not drawn anymore once the playback was skipped.
+    pub fn show(&mut self, frame: impl FnOnce() -> Frame) {
+        self.frames += 1;
+        let draws = self.draws && !self.skipped;
+        if !draws && !self.records() {
+            return;
+        }
+
+        let frame = frame();
+        self.record(&frame);
+        if !draws {
+            return;
+        }
+
+        self.draw(&frame, &self.status());
+
+        let due = Instant::now() + self.delay;
+        loop {
+            let timeout = if self.paused {
+                Duration::from_millis(100)
+            } else {
+                due.saturating_duration_since(Instant::now())
+            };
+
+            match self.next_control(timeout) {
+                Some(Control::TogglePause) => self.paused = !self.paused,
+                Some(Control::Step) if self.paused => return,
+                Some(Control::Faster) => self.delay /= 2,
+                Some(Control::Slower) => {
+                    self.delay = (self.delay * 2).clamp(Duration::from_millis(1), MAX_DELAY)
+                }
+                Some(Control::Skip) => {
+                    self.skipped = true;
+                    return;
+                }
+                Some(Control::Step) | None => {
+                    if !self.paused && Instant::now() >= due {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Show the last frame of a simulation, it is shown even if the playback was skipped.
+    /// Exported files are completed with it.
+    pub fn finish(&mut self, frame: &Frame) {
+        self.frames += 1;
+        self.record(frame);
+        self.finish_export();
+        if self.draws {
+            self.draw(frame, "done");
+        }
+    }
+
+    /// Whether the next frame is exported.
+    #[cfg(feature = "visualize-export")]
+    fn records(&self) -> bool {
+        self.exporter
+            .as_ref()
+            .is_some_and(|exporter| exporter.frames() < MAX_EXPORTED_FRAMES)
+    }
+
+    #[cfg(not(feature = "visualize-export"))]
+    fn records(&self) -> bool {
+        false
+    }
+
+    #[cfg(feature = "visualize-export")]
+    fn record(&mut self, frame: &Frame) {
+        let cells = self.paint(frame);
+        if let Some(exporter) = &mut self.exporter {
+            if let Err(e) = exporter.write(&cells, self.delay) {
+                eprintln!("Failed to export frames: {e}");
+                self.exporter = None;
+            }
+        }
+    }
+
+    #[cfg(not(feature = "visualize-export"))]
+    fn record(&mut self, _: &Frame) {}
+
+    #[cfg(feature = "visualize-export")]
+    fn finish_export(&mut self) {
+        let Some(exporter) = self.exporter.take() else {
+            return;
+        };
+
+        let exported = exporter.frames();
+        let count = if exported < self.frames {
+            format!("{exported} of {} frames", self.frames)
+        } else if exported == 1 {
+            "1 frame".into()
+        } else {
+            format!("{exported} frames")
+        };
+
+        match exporter.finish() {
+            Ok(path) => println!(
+                "{ANSI_ITALIC}{count} written to {}{ANSI_RESET}",
+                path.display()
+            ),
+            Err(e) => eprintln!("Failed to export frames: {e}"),
+        }
+    }
+
+    #[cfg(not(feature = "visualize-export"))]
+    fn finish_export(&mut self) {}
+
+    /// How the cells of a frame are drawn in exported images.
+    /// Highlighted cells without a colour of their own are filled with the highlight colour.
+    #[cfg(feature = "visualize-export")]
+    fn paint(&self, frame: &Frame) -> Vec<Vec<Paint>> {
+        frame
+            .cells
+            .iter()
+            .enumerate()
+            .map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(x, ch)| {
+                        match (self.colors.get(ch), frame.highlighted.contains(&(x, y))) {
+                            (Some(color), _) => Paint::Color(*color),
+                            (None, true) => Paint::Color(self.highlight),
+                            (None, false) if *ch == '.' || *ch == ' ' => Paint::Background,
+                            (None, false) => Paint::Foreground,
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn next_control(&self, timeout: Duration) -> Option<Control> {
+        match &self.controls {
+            Some(controls) => controls.receiver.recv_timeout(timeout).ok(),
+            None => {
+                thread::sleep(timeout);
+                None
+            }
+        }
+    }
+
+    fn status(&self) -> String {
+        if self.paused {
+            "paused · space resume, n step".into()
+        } else {
+            format!(
+                "{} fps · space pause, +/- speed, q skip",
+                (1.0 / self.delay.as_secs_f64().max(0.001)).round()
+            )
+        }
+    }
+
+    fn draw(&self, frame: &Frame, status: &str) {
+        let mut stdout = io::stdout().lock();
+        let _ = writeln!(
+            stdout,
+            "\x1b[2J\x1b[H{}{ANSI_ITALIC}frame {} · {status}{ANSI_RESET}",
+            self.render(frame),
+            self.frames
+        );
+        let _ = stdout.flush();
+    }
+
+    /// The frame as text with ANSI colours.
+    fn render(&self, frame: &Frame) -> String {
+        let mut s = String::new();
+
+        if let Some(caption) = &frame.caption {
+            s.push_str(&format!("{ANSI_BOLD}{caption}{ANSI_RESET}\n"));
+        }
+
+        for (y, row) in frame.cells.iter().enumerate() {
+            for (x, ch) in row.iter().enumerate() {
+                let color = self.colors.get(ch);
+                let highlighted = frame.highlighted.contains(&(x, y));
+
+                match (color, highlighted) {
+                    (None, false) => s.push(*ch),
+                    (Some(color), false) => {
+                        s.push_str(&format!("\x1b[{}m{ch}{ANSI_RESET}", color.code()))
+                    }
+                    (None, true) => s.push_str(&format!(
+                        "\x1b[{};30m{ch}{ANSI_RESET}",
+                        self.highlight.code() + 10
+                    )),
+                    (Some(color), true) => s.push_str(&format!(
+                        "\x1b[{};{}m{ch}{ANSI_RESET}",
+                        self.highlight.code() + 10,
+                        color.code()
+                    )),
+                }
+            }
+            s.push('\n');
+        }
+
+        s
+    }
+}
+
+impl Default for Visualizer {
+    /// A visualizer without key controls.
+    fn default() -> Self {
+        Self {
+            colors: HashMap::new(),
+            highlight: Color::Yellow,
+            delay: DEFAULT_DELAY,
+            paused: false,
+            skipped: false,
+            frames: 0,
+            draws: true,
+            controls: None,
+            #[cfg(feature = "visualize-export")]
+            exporter: None,
+        }
+    }
+}
+
+impl Drop for Visualizer {
+    /// Complete exported files of simulations that did not show a last frame.
+    fn drop(&mut self) {
+        self.finish_export();
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Color, Control, Frame, Visualizer};
+    use crate::template::ANSI_RESET;
+
+    #[test]
+    fn builds_frames() {
+        let board = vec![vec!['#', '.'], vec!['.', '#']];
+        let mut frame = Frame::new(&board);
+        frame.set(1, 0, 'O');
+        frame.set(5, 5, 'X');
+
+        assert_eq!(
+            frame,
+            Frame::from_fn(2, 2, |x, y| ['#', 'O', '.', '#'][y * 2 + x])
+        );
+        assert_eq!((frame.width(), frame.height()), (2, 2));
+    }
+
+    #[test]
+    fn renders_colors_and_highlights() {
+        let visualizer = Visualizer::default().color('#', Color::Gray);
+        let frame = Frame::from_fn(3, 1, |x, _| ['#', '.', '#'][x])
+            .highlight([(1, 0), (2, 0)])
+            .caption("step 1");
+
+        assert_eq!(
+            visualizer.render(&frame),
+            format!("\x1b[1mstep 1{ANSI_RESET}\n\x1b[90m#{ANSI_RESET}\x1b[43;30m.{ANSI_RESET}\x1b[43;90m#{ANSI_RESET}\n")
+        );
+    }
+
+    #[test]
+    fn maps_keys_to_controls() {
+        assert_eq!(Control::from_key(b' '), Some(Control::TogglePause));
+        assert_eq!(Control::from_key(b'n'), Some(Control::Step));
+        assert_eq!(Control::from_key(b'+'), Some(Control::Faster));
+        assert_eq!(Control::from_key(b'x'), None);
+    }
+}